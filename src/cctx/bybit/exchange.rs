@@ -0,0 +1,100 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use futures::FutureExt;
+
+use super::Bybit;
+use crate::cctx::*;
+
+// each entry overrides the trait method by the inherent method of the same name
+// and lists its ccxt name in implemented, so has() can't drift from the overrides
+macro_rules! implement {
+    ($($capability:literal => fn $method:ident($($arg:ident: $ty:ty),*) -> $ret:ty;)*) => {
+        fn implemented(&self) -> &'static [&'static str] {
+            &[$($capability),*]
+        }
+
+        $(
+            fn $method<'a>(&'a self, $($arg: $ty),*) -> ExchangeFuture<'a, $ret> {
+                Bybit::$method(self, $($arg),*).boxed()
+            }
+        )*
+    };
+}
+
+impl Exchange for Bybit {
+    fn id(&self) -> &'static str {
+        "bybit"
+    }
+
+    implement! {
+        "fetchTime" => fn fetch_time() -> i64;
+        "fetchCurrencies" => fn fetch_currencies() -> HashMap<String, Curreny>;
+        "loadMarkets" => fn load_markets(reload: bool) -> HashMap<String, Arc<Market>>;
+        "fetchTicker" => fn fetch_ticker(symbol: &'a str) -> Ticker;
+        "fetchTickers" => fn fetch_tickers(symbols: &'a [String]) -> HashMap<String, Ticker>;
+        "fetchBidsAsks" => fn fetch_bids_asks(symbols: &'a [String]) -> HashMap<String, Ticker>;
+        "fetchOrderBook" => fn fetch_order_book(symbol: &'a str, limit: Option<i64>) -> OrderBook;
+        "fetchTrades" => fn fetch_trades(symbol: &'a str, since: Option<i64>, limit: Option<i64>) -> Vec<Trade>;
+        "fetchOHLCV" => fn fetch_ohlcv(symbol: &'a str, timeframe: &'a str, since: Option<i64>, limit: Option<i64>) -> Vec<OHLCV>;
+        "fetchFundingRates" => fn fetch_funding_rates(symbols: &'a [String]) -> HashMap<String, FundingRate>;
+        "fetchFundingRateHistory" => fn fetch_funding_rate_history(symbol: &'a str, since: Option<i64>, limit: Option<i64>) -> Vec<FundingRateHistory>;
+        "fetchOpenInterest" => fn fetch_open_interest(symbol: &'a str) -> OpenInterest;
+        "fetchOpenInterestHistory" => fn fetch_open_interest_history(symbol: &'a str, timeframe: &'a str, since: Option<i64>, until: Option<i64>, limit: Option<i64>) -> Vec<OpenInterest>;
+        "fetchLongShortRatioHistory" => fn fetch_long_short_ratio_history(symbol: &'a str, timeframe: &'a str, since: Option<i64>, until: Option<i64>, limit: Option<i64>) -> Vec<LongShortRatio>;
+        "fetchVolatilityHistory" => fn fetch_volatility_history(code: &'a str, period: Option<i64>, since: Option<i64>) -> Vec<Volatility>;
+        "fetchGreeks" => fn fetch_greeks(symbol: &'a str) -> Greeks;
+        "fetchAllGreeks" => fn fetch_all_greeks(symbols: &'a [String]) -> HashMap<String, Greeks>;
+        "fetchOption" => fn fetch_option(symbol: &'a str) -> OptionContract;
+        "fetchOptionChain" => fn fetch_option_chain(underlying: &'a str) -> HashMap<String, OptionContract>;
+        "createOrder" => fn create_order(request: &'a OrderRequest) -> Order;
+        "createOrders" => fn create_orders(requests: &'a [OrderRequest]) -> Vec<Order>;
+        "createMarketBuyOrderWithCost" => fn create_market_buy_order_with_cost(symbol: &'a str, cost: f64) -> Order;
+        "createMarketSellOrderWithCost" => fn create_market_sell_order_with_cost(symbol: &'a str, cost: f64) -> Order;
+        "editOrder" => fn edit_order(id: &'a str, request: &'a OrderRequest) -> Order;
+        "editOrders" => fn edit_orders(orders: &'a [(String, OrderRequest)]) -> Vec<Order>;
+        "cancelOrder" => fn cancel_order(id: &'a str, symbol: &'a str) -> Order;
+        "cancelOrders" => fn cancel_orders(ids: &'a [String], symbol: &'a str) -> Vec<Order>;
+        "cancelOrdersForSymbols" => fn cancel_orders_for_symbols(orders: &'a [(String, String)]) -> Vec<Order>;
+        "cancelAllOrders" => fn cancel_all_orders(symbol: Option<&'a str>) -> Vec<Order>;
+        "cancelAllOrdersAfter" => fn cancel_all_orders_after(timeout: i64, product: Option<&'a str>) -> ();
+        "fetchBalance" => fn fetch_balance(account_type: Option<&'a str>) -> Balances;
+        "fetchOrder" => fn fetch_order(id: &'a str, symbol: &'a str) -> Order;
+        "fetchOpenOrders" => fn fetch_open_orders(symbol: Option<&'a str>, since: Option<i64>, limit: Option<i64>) -> Vec<Order>;
+        "fetchClosedOrders" => fn fetch_closed_orders(symbol: Option<&'a str>, since: Option<i64>, until: Option<i64>, limit: Option<i64>) -> Vec<Order>;
+        "fetchCanceledOrders" => fn fetch_canceled_orders(symbol: Option<&'a str>, since: Option<i64>, until: Option<i64>, limit: Option<i64>) -> Vec<Order>;
+        "fetchCanceledAndClosedOrders" => fn fetch_canceled_and_closed_orders(symbol: Option<&'a str>, since: Option<i64>, until: Option<i64>, limit: Option<i64>) -> Vec<Order>;
+        "fetchOrderTrades" => fn fetch_order_trades(id: &'a str, symbol: &'a str, since: Option<i64>, limit: Option<i64>) -> Vec<Trade>;
+        "fetchMyTrades" => fn fetch_my_trades(symbol: Option<&'a str>, since: Option<i64>, limit: Option<i64>) -> Vec<Trade>;
+        "fetchPosition" => fn fetch_position(symbol: &'a str) -> Position;
+        "fetchPositions" => fn fetch_positions(symbols: &'a [String]) -> Vec<Position>;
+        "fetchPositionsHistory" => fn fetch_positions_history(symbol: Option<&'a str>, since: Option<i64>, limit: Option<i64>) -> Vec<Position>;
+        "fetchLeverage" => fn fetch_leverage(symbol: &'a str) -> Leverage;
+        "setLeverage" => fn set_leverage(leverage: f64, symbol: &'a str) -> ();
+        "setMarginMode" => fn set_margin_mode(margin_mode: &'a str, symbol: Option<&'a str>) -> ();
+        "setPositionMode" => fn set_position_mode(hedged: bool, symbol: Option<&'a str>) -> ();
+        "fetchDepositAddress" => fn fetch_deposit_address(code: &'a str, network: Option<&'a str>) -> DepositAddress;
+        "fetchDepositAddressesByNetwork" => fn fetch_deposit_addresses_by_network(code: &'a str, network: Option<&'a str>) -> HashMap<String, DepositAddress>;
+        "fetchDeposits" => fn fetch_deposits(code: Option<&'a str>, since: Option<i64>, limit: Option<i64>) -> Vec<Transaction>;
+        "fetchWithdrawals" => fn fetch_withdrawals(code: Option<&'a str>, since: Option<i64>, limit: Option<i64>) -> Vec<Transaction>;
+        "withdraw" => fn withdraw(code: &'a str, amount: f64, address: &'a str, tag: Option<&'a str>, network: Option<&'a str>) -> Transaction;
+        "transfer" => fn transfer(code: &'a str, amount: f64, from_account: &'a str, to_account: &'a str) -> TransferEntry;
+        "fetchTransfers" => fn fetch_transfers(code: Option<&'a str>, since: Option<i64>, limit: Option<i64>) -> Vec<TransferEntry>;
+        "fetchLedger" => fn fetch_ledger(code: Option<&'a str>, since: Option<i64>, limit: Option<i64>) -> Vec<LedgerEntry>;
+        "fetchCrossBorrowRate" => fn fetch_cross_borrow_rate(code: &'a str) -> BorrowRate;
+        "fetchBorrowRateHistory" => fn fetch_borrow_rate_history(code: &'a str, since: Option<i64>, limit: Option<i64>) -> Vec<BorrowRate>;
+        "fetchBorrowInterest" => fn fetch_borrow_interest(code: Option<&'a str>, since: Option<i64>, limit: Option<i64>) -> Vec<BorrowInterest>;
+        "borrowCrossMargin" => fn borrow_cross_margin(code: &'a str, amount: f64) -> MarginLoan;
+        "repayCrossMargin" => fn repay_cross_margin(code: &'a str, amount: Option<f64>) -> MarginLoan;
+        "fetchLeverageTiers" => fn fetch_leverage_tiers(symbols: &'a [String]) -> HashMap<String, Vec<LeverageTier>>;
+        "fetchMarketLeverageTiers" => fn fetch_market_leverage_tiers(symbol: &'a str) -> Vec<LeverageTier>;
+        "fetchTradingFee" => fn fetch_trading_fee(symbol: &'a str) -> TradingFee;
+        "fetchTradingFees" => fn fetch_trading_fees() -> HashMap<String, TradingFee>;
+        "fetchDepositWithdrawFees" => fn fetch_deposit_withdraw_fees(codes: &'a [String]) -> HashMap<String, DepositWithdrawFee>;
+        "fetchConvertCurrencies" => fn fetch_convert_currencies() -> HashMap<String, Curreny>;
+        "fetchConvertQuote" => fn fetch_convert_quote(from_code: &'a str, to_code: &'a str, amount: f64) -> Conversion;
+        "createConvertTrade" => fn create_convert_trade(quote: &'a Conversion) -> Conversion;
+        "fetchConvertTrade" => fn fetch_convert_trade(id: &'a str) -> Conversion;
+        "fetchConvertTradeHistory" => fn fetch_convert_trade_history(code: Option<&'a str>, since: Option<i64>, limit: Option<i64>) -> Vec<Conversion>;
+    }
+}
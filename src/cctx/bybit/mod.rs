@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
 
 use anyhow::Result;
 use chrono::{DateTime, Utc};
@@ -7,6 +8,9 @@ use reqwest::Client;
 use serde::Serialize;
 use sha2::Sha256;
 
+use crate::cctx::Market;
+
+pub mod exchange;
 pub mod oneshot;
 pub mod watch;
 
@@ -19,6 +23,60 @@ pub struct Bybit {
     api_secret: String,
     http_client: reqwest::Client,
     pub option: BybitOptions,
+    markets: RwLock<Markets>,
+}
+
+#[derive(Debug, Default)]
+pub struct Markets {
+    pub loaded: bool,
+    pub by_symbol: HashMap<String, Arc<Market>>,
+    // (category, id), the same id can be both spot and linear, e.g. BTCUSDT
+    pub by_id: HashMap<(String, String), Arc<Market>>,
+}
+
+const DAY: i64 = 24 * 60 * 60 * 1000;
+
+// list endpoint read by fetch_paged
+struct PagedApi<'a> {
+    path: &'a str,
+    // signed private endpoint, otherwise public
+    private: bool,
+    // max size of a page
+    page_limit: i64,
+    // max range between startTime and endTime in ms, None when the endpoint has no time range
+    window: Option<i64>,
+    // field of the row time, rows of a window are sorted by it
+    time_key: &'a str,
+}
+
+impl<'a> PagedApi<'a> {
+    fn public(path: &'a str, page_limit: i64) -> Self {
+        Self {
+            path,
+            private: false,
+            page_limit,
+            window: None,
+            time_key: "",
+        }
+    }
+
+    fn private(path: &'a str, page_limit: i64) -> Self {
+        Self {
+            path,
+            private: true,
+            page_limit,
+            window: None,
+            time_key: "",
+        }
+    }
+
+    fn window(self, window: i64, time_key: &'a str) -> Self {
+        Self {
+            window: Some(window),
+            time_key,
+            ..self
+        }
+    }
 }
 
 // TODO: imply default
@@ -28,6 +86,10 @@ pub struct BybitOptions {
     pub account_by_id: HashMap<String, String>,
     pub networks: HashMap<String, String>,
     pub networks_by_id: HashMap<String, String>,
+    // fill maker and taker of markets with the fee rates of the account in load_markets, requires api key
+    pub load_trading_fees: bool,
+    // eb_convert_funding, eb_convert_uta, eb_convert_spot, eb_convert_contract, eb_convert_inverse
+    pub convert_account_type: String,
 }
 
 impl Default for BybitOptions {
@@ -151,6 +213,8 @@ impl Default for BybitOptions {
             account_by_id,
             networks,
             networks_by_id,
+            load_trading_fees: false,
+            convert_account_type: "eb_convert_funding".to_string(),
         }
     }
 }
@@ -166,6 +230,7 @@ impl Bybit {
             api_secret: api_secret.to_string(),
             option: BybitOptions::default(),
             http_client,
+            markets: RwLock::default(),
         })
     }
 }
@@ -175,6 +240,54 @@ pub struct Api {
     pub server_time: &'static str,
     pub coin_info: &'static str,
     pub market_info: &'static str,
+    pub tickers: &'static str,
+    pub order_book: &'static str,
+    pub recent_trade: &'static str,
+    pub kline: &'static str,
+    pub funding_rate_history: &'static str,
+    pub open_interest: &'static str,
+    pub long_short_ratio: &'static str,
+    pub volatility_history: &'static str,
+    pub risk_limit: &'static str,
+    pub create_order: &'static str,
+    pub create_orders: &'static str,
+    pub edit_order: &'static str,
+    pub edit_orders: &'static str,
+    pub cancel_order: &'static str,
+    pub cancel_orders: &'static str,
+    pub cancel_all_orders: &'static str,
+    pub cancel_all_orders_after: &'static str,
+    pub wallet_balance: &'static str,
+    pub fund_balance: &'static str,
+    pub open_orders: &'static str,
+    pub order_history: &'static str,
+    pub executions: &'static str,
+    pub positions: &'static str,
+    pub closed_pnl: &'static str,
+    pub set_leverage: &'static str,
+    pub set_margin_mode: &'static str,
+    pub switch_isolated: &'static str,
+    pub set_position_mode: &'static str,
+    pub account_info: &'static str,
+    pub deposit_address: &'static str,
+    pub deposits: &'static str,
+    pub withdrawals: &'static str,
+    pub withdraw: &'static str,
+    pub transfer: &'static str,
+    pub transfers: &'static str,
+    pub transaction_log: &'static str,
+    pub contract_transaction_log: &'static str,
+    pub collateral_info: &'static str,
+    pub borrow_rate_history: &'static str,
+    pub borrow_history: &'static str,
+    pub borrow: &'static str,
+    pub repay: &'static str,
+    pub fee_rate: &'static str,
+    pub convert_coins: &'static str,
+    pub convert_quote: &'static str,
+    pub convert_execute: &'static str,
+    pub convert_result: &'static str,
+    pub convert_history: &'static str,
 }
 
 impl Default for Api {
@@ -183,10 +296,221 @@ impl Default for Api {
             server_time: "v5/market/time",
             coin_info: "v5/asset/coin/query-info",
             market_info: "v5/market/instruments-info",
+            tickers: "v5/market/tickers",
+            order_book: "v5/market/orderbook",
+            recent_trade: "v5/market/recent-trade",
+            kline: "v5/market/kline",
+            funding_rate_history: "v5/market/funding/history",
+            open_interest: "v5/market/open-interest",
+            long_short_ratio: "v5/market/account-ratio",
+            volatility_history: "v5/market/historical-volatility",
+            risk_limit: "v5/market/risk-limit",
+            create_order: "v5/order/create",
+            create_orders: "v5/order/create-batch",
+            edit_order: "v5/order/amend",
+            edit_orders: "v5/order/amend-batch",
+            cancel_order: "v5/order/cancel",
+            cancel_orders: "v5/order/cancel-batch",
+            cancel_all_orders: "v5/order/cancel-all",
+            cancel_all_orders_after: "v5/order/disconnected-cancel-all",
+            wallet_balance: "v5/account/wallet-balance",
+            fund_balance: "v5/asset/transfer/query-account-coins-balance",
+            open_orders: "v5/order/realtime",
+            order_history: "v5/order/history",
+            executions: "v5/execution/list",
+            positions: "v5/position/list",
+            closed_pnl: "v5/position/closed-pnl",
+            set_leverage: "v5/position/set-leverage",
+            set_margin_mode: "v5/account/set-margin-mode",
+            switch_isolated: "v5/position/switch-isolated",
+            set_position_mode: "v5/position/switch-mode",
+            account_info: "v5/account/info",
+            deposit_address: "v5/asset/deposit/query-address",
+            deposits: "v5/asset/deposit/query-record",
+            withdrawals: "v5/asset/withdraw/query-record",
+            withdraw: "v5/asset/withdraw/create",
+            transfer: "v5/asset/transfer/inter-transfer",
+            transfers: "v5/asset/transfer/query-inter-transfer-list",
+            transaction_log: "v5/account/transaction-log",
+            contract_transaction_log: "v5/account/contract-transaction-log",
+            collateral_info: "v5/account/collateral-info",
+            borrow_rate_history: "v5/spot-margin-trade/interest-rate-history",
+            borrow_history: "v5/account/borrow-history",
+            borrow: "v5/account/borrow",
+            repay: "v5/account/repay",
+            fee_rate: "v5/account/fee-rate",
+            convert_coins: "v5/asset/exchange/query-coin-list",
+            convert_quote: "v5/asset/exchange/quote-apply",
+            convert_execute: "v5/asset/exchange/convert-execute",
+            convert_result: "v5/asset/exchange/convert-result-query",
+            convert_history: "v5/asset/exchange/query-convert-history",
         }
     }
 }
 
+// category used by v5 api: spot, linear, inverse, option
+fn category(market: &Market) -> &'static str {
+    if market.option {
+        "option"
+    } else if market.inverse.unwrap_or_default() {
+        "inverse"
+    } else if market.contract {
+        "linear"
+    } else {
+        "spot"
+    }
+}
+
+// ccxt timeframe -> (bybit interval, duration in millisecond)
+fn timeframe(timeframe: &str) -> Option<(&'static str, i64)> {
+    const MINUTE: i64 = 60 * 1000;
+    let res = match timeframe {
+        "1m" => ("1", MINUTE),
+        "3m" => ("3", 3 * MINUTE),
+        "5m" => ("5", 5 * MINUTE),
+        "15m" => ("15", 15 * MINUTE),
+        "30m" => ("30", 30 * MINUTE),
+        "1h" => ("60", 60 * MINUTE),
+        "2h" => ("120", 120 * MINUTE),
+        "4h" => ("240", 240 * MINUTE),
+        "6h" => ("360", 360 * MINUTE),
+        "12h" => ("720", 720 * MINUTE),
+        "1d" => ("D", 1440 * MINUTE),
+        "1w" => ("W", 7 * 1440 * MINUTE),
+        "1M" => ("M", 30 * 1440 * MINUTE),
+        _ => return None,
+    };
+    Some(res)
+}
+
+// ccxt timeframe -> intervalTime of open interest and period of long short ratio
+fn interval_time(timeframe: &str) -> Option<&'static str> {
+    let res = match timeframe {
+        "5m" => "5min",
+        "15m" => "15min",
+        "30m" => "30min",
+        "1h" => "1h",
+        "4h" => "4h",
+        "1d" => "1d",
+        _ => return None,
+    };
+    Some(res)
+}
+
+// bybit order status -> ccxt order status
+fn order_status(status: &str) -> &str {
+    match status {
+        "Created" | "New" | "PartiallyFilled" | "PendingCancel" | "Untriggered" | "Triggered" | "Active" => "open",
+        "Filled" | "PartiallyFilledCanceled" => "closed",
+        "Cancelled" | "Deactivated" => "canceled",
+        "Rejected" => "rejected",
+        _ => status,
+    }
+}
+
+// execution types of trading, the others are funding, settlement and delivery
+fn is_fill(exec_type: &str) -> bool {
+    matches!(
+        exec_type,
+        "Trade" | "AdlTrade" | "BustTrade" | "BlockTrade" | "MovePosition" | "FutureSpread"
+    )
+}
+
+// bybit deposit and withdrawal status -> pending, ok, failed, canceled
+fn transaction_status(status: &str) -> &str {
+    match status {
+        // deposit
+        "0" | "1" | "2" | "10011" => "pending",
+        "3" | "10012" => "ok",
+        "4" => "failed",
+        // withdrawal
+        "SecurityCheck" | "Pending" | "MoreInformationRequired" => "pending",
+        "success" | "BlockchainConfirmed" => "ok",
+        "Reject" | "Fail" => "failed",
+        "CancelByUser" => "canceled",
+        _ => status,
+    }
+}
+
+// bybit transfer status -> pending, ok, failed
+fn transfer_status(status: &str) -> &str {
+    match status {
+        "SUCCESS" => "ok",
+        "PENDING" => "pending",
+        "FAILED" => "failed",
+        _ => status,
+    }
+}
+
+// bybit transaction log type -> ccxt ledger entry type
+fn ledger_entry_type(r#type: &str) -> &str {
+    match r#type {
+        "TRANSFER_IN" | "TRANSFER_OUT" => "transfer",
+        "TRADE" | "SETTLEMENT" | "DELIVERY" | "LIQUIDATION" | "ADL" | "CURRENCY_BUY" | "CURRENCY_SELL" => "trade",
+        "BONUS" => "prize",
+        "FEE_REFUND" => "cashback",
+        "INTEREST" => "interest",
+        "AIRDRP" => "airdrop",
+        _ => r#type,
+    }
+}
+
+// bybit convert status -> pending, ok, failed
+fn convert_status(status: &str) -> &str {
+    match status {
+        "init" | "processing" => "pending",
+        "success" => "ok",
+        "failure" => "failed",
+        _ => status,
+    }
+}
+
+// keeps limit entries of list sorted by time like ccxt filterBySinceLimit,
+// the earliest ones when fetched from since, otherwise the latest ones
+fn filter_by_limit<T>(list: &mut Vec<T>, limit: Option<i64>, from_start: bool) {
+    let Some(limit) = limit else {
+        return;
+    };
+    let limit = limit.max(0) as usize;
+    if from_start {
+        list.truncate(limit);
+    } else {
+        list.drain(..list.len().saturating_sub(limit));
+    }
+}
+
+// max number of orders of a batch request, 10 for spot and 20 for linear, inverse and option
+fn batch_limit(category: &str) -> usize {
+    if category == "spot" { 10 } else { 20 }
+}
+
+// key of the fee rate of market, option fee rates are per base coin
+fn trading_fee_key(market: &Market) -> (String, String) {
+    let id = if market.option { &market.base_id } else { &market.id };
+    (category(market).to_string(), id.clone())
+}
+
+// format value as a multiple of step, amount should be truncated while price is rounded
+fn to_precision(value: f64, step: Option<f64>, truncate: bool) -> String {
+    let Some(step) = step.filter(|v| *v > 0.0) else {
+        return value.to_string();
+    };
+    let decimals = step.to_string().split_once('.').map_or(0, |(_, v)| v.len());
+    // tolerate float error like 0.30000000000000004 / 0.1
+    let steps = value / step;
+    let steps = if truncate {
+        (steps + 1e-9).floor()
+    } else {
+        steps.round()
+    };
+    format!("{:.*}", decimals, steps * step)
+}
+
+// number of decimals -> step, e.g. 3 -> 0.001
+fn decimals_to_step(decimals: i64) -> Option<f64> {
+    format!("1e-{decimals}").parse().ok()
+}
+
 fn timestamp() -> i64 {
     std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap().as_millis().try_into().unwrap()
 }
@@ -213,6 +537,64 @@ fn yymmdd(timestamp: i64) -> Option<String> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::cctx::Exchange;
+
+    #[test]
+    fn test_has() {
+        let exchange: Box<dyn Exchange> = Box::new(Bybit::new("123", "456").unwrap());
+        let has = exchange.has();
+        assert!(has.len() == crate::cctx::CAPABILITIES.len());
+        assert!(has.get("fetchTime") == Some(&true));
+        assert!(has.get("fetchTicker") == Some(&true));
+        assert!(has.get("editOrders") == Some(&true));
+        assert!(has.get("fetchGreeks") == Some(&true));
+        assert!(has.get("cancelAllOrdersAfter") == Some(&true));
+
+        let implemented = exchange.implemented();
+        let unique: std::collections::HashSet<_> = implemented.iter().collect();
+        assert!(unique.len() == implemented.len());
+        assert!(implemented.iter().all(|name| crate::cctx::CAPABILITIES.contains(name)));
+    }
+
+    #[test]
+    fn test_filter_by_limit() {
+        let mut list = vec![1, 2, 3, 4, 5];
+        filter_by_limit(&mut list, Some(2), false);
+        assert!(list == [4, 5]);
+        let mut list = vec![1, 2, 3, 4, 5];
+        filter_by_limit(&mut list, Some(2), true);
+        assert!(list == [1, 2]);
+        let mut list = vec![1, 2, 3];
+        filter_by_limit(&mut list, Some(-1), false);
+        assert!(list.is_empty());
+        let mut list = vec![1, 2, 3];
+        filter_by_limit(&mut list, None, false);
+        assert!(list == [1, 2, 3]);
+    }
+
+    #[test]
+    fn test_timeframe() {
+        assert!(timeframe("1m") == Some(("1", 60_000)));
+        assert!(timeframe("1d") == Some(("D", 86_400_000)));
+        assert!(timeframe("1y").is_none());
+    }
+
+    #[test]
+    fn test_to_precision() {
+        assert!(to_precision(0.3, Some(0.1), true) == "0.3");
+        assert!(to_precision(0.129, Some(0.01), true) == "0.12");
+        assert!(to_precision(90000.26, Some(0.5), false) == "90000.5");
+        assert!(to_precision(12.0, Some(1.0), true) == "12");
+        assert!(to_precision(1.5, None, true) == "1.5");
+    }
+
+    #[test]
+    fn test_decimals_to_step() {
+        assert!(decimals_to_step(3) == Some(0.001));
+        assert!(to_precision(0.1 + 0.2, decimals_to_step(8), true) == "0.30000000");
+        assert!(to_precision(1.23456, decimals_to_step(3), true) == "1.234");
+    }
+
     #[test]
     fn test_hmax_sha256() {
         let x = hmax_sha256(
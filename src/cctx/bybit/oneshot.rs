@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
 use crate::cctx::*;
 
@@ -48,13 +49,16 @@ impl Bybit {
                     let Some(network_id) = chain.get("chain").and_then(|v| v.as_str()) else {
                         return;
                     };
-                    let network_code = self.option.networks_by_id.get(network_id).map_or(network_id, |v| v);
+                    let network_code = self.network_code(network_id);
+                    // chainDeposit and chainWithdraw: 1 enabled, 0 suspended
+                    let deposit = chain.get("chainDeposit").and_then(|v| v.a_o_p_i64()).map(|v| v == 1);
+                    let withdraw = chain.get("chainWithdraw").and_then(|v| v.a_o_p_i64()).map(|v| v == 1);
                     let network = Network {
                         info: chain.clone(),
                         id: network_id.to_string(),
                         network: network_code.to_string(),
-                        active: None,
-                        name: None,
+                        active: deposit.zip(withdraw).map(|(deposit, withdraw)| deposit && withdraw),
+                        name: chain.get("chainType").and_then(|v| v.as_str()).map(|v| v.to_string()),
                         fee: chain.get("withdrawFee").and_then(|v| v.a_o_p_f64()),
                         precision: chain.get("minAccuracy").and_then(|v| v.a_o_p_i64()),
                         limits: CurrenyLimits {
@@ -68,24 +72,37 @@ impl Bybit {
                             },
                             amount: Limit { min: None, max: None },
                         },
-                        deposit: None,
-                        withdraw: None,
+                        deposit,
+                        withdraw,
                     };
                     networks.insert(network_code.to_string(), network);
                 });
             });
 
+            // aggregates of networks: enabled when any network is, the lowest fee and limits, the finest precision
+            let any = |f: fn(&Network) -> Option<bool>| networks.values().filter_map(f).reduce(|a, b| a || b);
+            let min = |f: fn(&Network) -> Option<f64>| networks.values().filter_map(f).reduce(f64::min);
             let currency_item = Curreny {
                 info: row.clone(),
                 code: code.to_string(),
                 id: currenty_id.to_string(),
                 name: name.to_string(),
-                active: None,
-                deposit: None,
-                withdraw: None,
-                fee: None,
-                precision: None,
-                limits: CurrenyLimits::default(),
+                active: any(|v| v.active),
+                deposit: any(|v| v.deposit),
+                withdraw: any(|v| v.withdraw),
+                fee: min(|v| v.fee),
+                precision: networks.values().filter_map(|v| v.precision).max(),
+                limits: CurrenyLimits {
+                    withdraw: Limit {
+                        min: min(|v| v.limits.withdraw.min),
+                        max: None,
+                    },
+                    deposit: Limit {
+                        min: min(|v| v.limits.deposit.min),
+                        max: None,
+                    },
+                    amount: Limit { min: None, max: None },
+                },
                 r#type: "crypto".to_string(),
                 networks,
             };
@@ -94,6 +111,50 @@ impl Bybit {
         Ok(res)
     }
 
+    // code -> fees of currencies in codes, all currencies when codes is empty
+    pub async fn fetch_deposit_withdraw_fees(&self, codes: &[String]) -> Result<HashMap<String, DepositWithdrawFee>> {
+        let currencies = self.fetch_currencies().await?;
+        let mut res = HashMap::new();
+        for (code, currency) in currencies {
+            if !codes.is_empty() && !codes.contains(&code) {
+                continue;
+            }
+            let mut networks = HashMap::new();
+            for (network_code, network) in &currency.networks {
+                // withdrawPercentageFee is a rate of the amount, e.g. 0.022 for 2.2%, instead of withdrawFee
+                let percentage_fee = network.info.get("withdrawPercentageFee").and_then(|v| v.a_o_p_f64());
+                let withdraw = match percentage_fee.filter(|v| *v > 0.0) {
+                    Some(rate) => TransactionFee {
+                        fee: Some(rate),
+                        percentage: Some(true),
+                    },
+                    None => TransactionFee {
+                        fee: network.fee,
+                        percentage: network.fee.map(|_| false),
+                    },
+                };
+                let fee = DepositWithdrawFeeNetwork {
+                    withdraw,
+                    deposit: TransactionFee::default(),
+                };
+                networks.insert(network_code.clone(), fee);
+            }
+            // the fee of the currency is only unambiguous with a single network
+            let withdraw = match networks.values().collect::<Vec<_>>().as_slice() {
+                [network] => network.withdraw.clone(),
+                _ => TransactionFee::default(),
+            };
+            let fee = DepositWithdrawFee {
+                withdraw,
+                deposit: TransactionFee::default(),
+                networks,
+                info: currency.info,
+            };
+            res.insert(code, fee);
+        }
+        Ok(res)
+    }
+
     async fn fetch_spot_markets(&self, query: &[(String, String)]) -> Result<Vec<Market>> {
         let url = self.new_url(self.api.market_info)?;
         let query = query.append_q(&("category", "spot"));
@@ -202,15 +263,16 @@ impl Bybit {
             self.http_client.get(url.clone()).query(&pre_query).send().await?.json::<Value>()
         );
         let mut resp = resp?;
-        let mut pre_resp = pre_resp?;
+        let pre_resp = pre_resp?;
         self.check_resp(&resp)?;
         self.check_resp(&pre_resp)?;
         let result = resp.get_mut("result").context("no result")?;
-        let pre_result = pre_resp.get_mut("result").context("no result")?;
+        let pre_result = pre_resp.get("result").context("no result")?;
+        let category = result.get("category").and_then(|v| v.as_str()).unwrap_or_default().to_string();
         let mut next_cursor =
             result.get("nextPageCursor").and_then(|v| v.as_str()).map_or(String::new(), |v| v.to_string());
         let markets = result.get_mut("list").context("no list")?.as_array_mut().context("list not array")?;
-        if let Some(pre_markets) = pre_resp.get("list").and_then(|v| v.as_array()) {
+        if let Some(pre_markets) = pre_result.get("list").and_then(|v| v.as_array()) {
             markets.extend_from_slice(pre_markets);
         }
         while !next_cursor.is_empty() {
@@ -224,13 +286,8 @@ impl Bybit {
                 markets.extend_from_slice(new_markets);
             }
         }
-        // let mut res = Vec::new();
-
+        let mut res = Vec::new();
         for market in markets {
-            let Some(category) = market.get("category").and_then(|v| v.as_str()) else {
-                continue;
-            };
-
             // var linear interface{} = (IsEqual(category, "linear"))
             // var inverse interface{} = (IsEqual(category, "inverse"))
             // var contractType interface{} = this.SafeString(market, "contractType")
@@ -296,7 +353,6 @@ impl Bybit {
             };
             let base = self.get_currency_code(base_id);
             let quote = self.get_currency_code(quote_id);
-            let symbol = format!("{base}/{quote}");
             let default_settled_id = if linear { quote_id } else { base_id };
             let settle_id = market.get("settleCoin").and_then(|v| v.as_str()).unwrap_or(default_settled_id);
             let settle = if linear_perpetual && (settle_id == "USD") {
@@ -304,11 +360,10 @@ impl Bybit {
             } else {
                 self.get_currency_code(settle_id)
             };
-            let symbol = format!("{base}/{quote}");
             let lot_size_filter = market.get("lotSizeFilter");
             let price_filter = market.get("priceFilter");
             let leverage = market.get("leverageFilter");
-            let status = market.get("status").and_then(|v| v.as_str());
+            let active = market.get("status").and_then(|v| v.as_str()).is_some_and(|status| status == "Trading");
             let swap = linear_perpetual || inverse_perpetual;
             let future = inverse_future || linear_future;
             let type_var = if swap {
@@ -318,33 +373,191 @@ impl Bybit {
             } else {
                 String::new()
             };
+            // some swaps have deliveryTime meaning delisting time
             let expiry = if !swap {
-                market
-                    .get("deliveryTime")
-                    .and_then(|v| v.a_o_p_f64())
-                    .and_then(|v| if v == 0.0 { None } else { Some(v) })
-                    .map(|v| v as i64)
+                market.get("deliveryTime").and_then(|v| v.a_o_p_i64()).filter(|v| *v != 0)
             } else {
                 None
             };
-            let mut symbol = format!("{symbol}:{settle}");
+            let mut symbol = format!("{base}/{quote}:{settle}");
             if let Some(ts) = expiry {
-                symbol = format!("{symbol}-{}", yymmdd(ts * 1000).unwrap_or_default());
+                symbol = format!("{symbol}-{}", yymmdd(ts).unwrap_or_default());
             }
-            let expiry_data_time = expiry.and_then(|v| iso_8601(v));
-            let contract_size = if inverse { 
-                // lot_size_filter.as_ref().get("minTradingQty")
-             } else { Some(1.0f) };
+            let expiry_datetime = expiry.and_then(iso_8601);
+            let contract_size = if inverse {
+                lot_size_filter
+                    .and_then(|v| v.get("minTradingQty").or(v.get("minOrderQty")).and_then(|v| v.a_o_p_f64()))
+            } else {
+                Some(1.0)
+            };
+            let item = Market {
+                id: id.to_string(),
+                symbol,
+                base,
+                quote,
+                base_id: base_id.to_string(),
+                quote_id: quote_id.to_string(),
+                active,
+                r#type: type_var,
+                spot: false,
+                margin: None,
+                future,
+                swap,
+                option: false,
+                contract: true,
+                settle: Some(settle),
+                settle_id: Some(settle_id.to_string()),
+                contract_size,
+                linear: Some(linear),
+                inverse: Some(inverse),
+                expiry,
+                expiry_datetime,
+                strike: None,
+                option_type: None,
+                taker: None,
+                maker: None,
+                percentage: None,
+                tier_based: None,
+                fee_side: None,
+                precision: MarketPrecision {
+                    amount: lot_size_filter.and_then(|v| v.get("qtyStep").and_then(|v| v.a_o_p_f64())),
+                    price: price_filter.and_then(|v| v.get("tickSize").and_then(|v| v.a_o_p_f64())),
+                    cost: None,
+                },
+                limits: MarketLimits {
+                    amount: Limit {
+                        min: lot_size_filter
+                            .and_then(|v| v.get("minOrderQty").or(v.get("minTradingQty")).and_then(|v| v.a_o_p_f64())),
+                        max: lot_size_filter
+                            .and_then(|v| v.get("maxOrderQty").or(v.get("maxTradingQty")).and_then(|v| v.a_o_p_f64())),
+                    },
+                    price: Limit {
+                        min: price_filter.and_then(|v| v.get("minPrice").and_then(|v| v.a_o_p_f64())),
+                        max: price_filter.and_then(|v| v.get("maxPrice").and_then(|v| v.a_o_p_f64())),
+                    },
+                    cost: Limit { min: None, max: None },
+                    leverage: Limit {
+                        min: leverage.and_then(|v| v.get("minLeverage").and_then(|v| v.a_o_p_f64())),
+                        max: leverage.and_then(|v| v.get("maxLeverage").and_then(|v| v.a_o_p_f64())),
+                    },
+                },
+                margin_modes: None,
+                created: None,
+                info: market.clone(),
+            };
+            res.push(item);
         }
-
-        todo!()
+        Ok(res)
     }
 
     async fn fetch_option_markets(&self, query: &[(String, String)]) -> Result<Vec<Market>> {
-        todo!()
+        let url = self.new_url(self.api.market_info)?;
+        let query = query.extend_q(&[("category", "option"), ("limit", "1000")]);
+        let mut resp = self.http_client.get(url.clone()).query(&query).send().await?.json::<Value>().await?;
+        self.check_resp(&resp)?;
+        let result = resp.get_mut("result").context("no result")?;
+        let mut next_cursor =
+            result.get("nextPageCursor").and_then(|v| v.as_str()).map_or(String::new(), |v| v.to_string());
+        let markets = result.get_mut("list").context("no list")?.as_array_mut().context("list not array")?;
+        while !next_cursor.is_empty() {
+            let new_query = query.append_q(&("cursor", &next_cursor));
+            let resp = self.http_client.get(url.clone()).query(&new_query).send().await?.json::<Value>().await?;
+            self.check_resp(&resp)?;
+            let result = resp.get("result").context("no result")?;
+            next_cursor =
+                result.get("nextPageCursor").and_then(|v| v.as_str()).map_or(String::new(), |v| v.to_string());
+            let new_markets = result.get("list").context("no list")?.as_array().context("list not array")?;
+            markets.extend_from_slice(new_markets);
+        }
+
+        let mut res = Vec::new();
+        for market in markets {
+            // option id looks like BTC-26DEC25-100000-C or BTC-26DEC25-100000-C-USDT
+            let Some(id) = market.get("symbol").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let Some(base_id) = market.get("baseCoin").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let Some(quote_id) = market.get("quoteCoin").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let Some(settle_id) = market.get("settleCoin").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let split_id = id.split('-').collect::<Vec<_>>();
+            let (Some(strike), Some(option_letter)) = (split_id.get(2), split_id.get(3)) else {
+                continue;
+            };
+            let Some(expiry) = market.get("deliveryTime").and_then(|v| v.a_o_p_i64()).filter(|v| *v != 0) else {
+                continue;
+            };
+            let base = self.get_currency_code(base_id);
+            let quote = self.get_currency_code(quote_id);
+            let settle = self.get_currency_code(settle_id);
+            let symbol = format!(
+                "{base}/{quote}:{settle}-{}-{strike}-{option_letter}",
+                yymmdd(expiry).unwrap_or_default()
+            );
+            let active = market.get("status").and_then(|v| v.as_str()).is_some_and(|status| status == "Trading");
+            let lot_size_filter = market.get("lotSizeFilter");
+            let price_filter = market.get("priceFilter");
+            let item = Market {
+                id: id.to_string(),
+                symbol,
+                base,
+                quote,
+                base_id: base_id.to_string(),
+                quote_id: quote_id.to_string(),
+                active,
+                r#type: "option".to_string(),
+                spot: false,
+                margin: Some(false),
+                future: false,
+                swap: false,
+                option: true,
+                contract: true,
+                settle: Some(settle),
+                settle_id: Some(settle_id.to_string()),
+                contract_size: Some(1.0),
+                linear: None,
+                inverse: None,
+                expiry: Some(expiry),
+                expiry_datetime: iso_8601(expiry),
+                strike: strike.parse::<f64>().ok(),
+                option_type: market.get("optionsType").and_then(|v| v.as_str()).map(|v| v.to_lowercase()),
+                taker: None,
+                maker: None,
+                percentage: None,
+                tier_based: None,
+                fee_side: None,
+                precision: MarketPrecision {
+                    amount: lot_size_filter.and_then(|v| v.get("qtyStep").and_then(|v| v.a_o_p_f64())),
+                    price: price_filter.and_then(|v| v.get("tickSize").and_then(|v| v.a_o_p_f64())),
+                    cost: None,
+                },
+                limits: MarketLimits {
+                    amount: Limit {
+                        min: lot_size_filter.and_then(|v| v.get("minOrderQty").and_then(|v| v.a_o_p_f64())),
+                        max: lot_size_filter.and_then(|v| v.get("maxOrderQty").and_then(|v| v.a_o_p_f64())),
+                    },
+                    price: Limit {
+                        min: price_filter.and_then(|v| v.get("minPrice").and_then(|v| v.a_o_p_f64())),
+                        max: price_filter.and_then(|v| v.get("maxPrice").and_then(|v| v.a_o_p_f64())),
+                    },
+                    cost: Limit { min: None, max: None },
+                    leverage: Limit { min: None, max: None },
+                },
+                margin_modes: None,
+                created: None,
+                info: market.clone(),
+            };
+            res.push(item);
+        }
+        Ok(res)
     }
 
-    async fn fetch_markets(&self, query: &[(String, String)]) -> Result<Vec<Market>> {
+    pub async fn fetch_markets(&self, query: &[(String, String)]) -> Result<Vec<Market>> {
         // https://bybit-exchange.github.io/docs/v5/market/instrument
 
         let linear_query = query.append_q(&("category", "linear"));
@@ -376,6 +589,2754 @@ impl Bybit {
         Ok(spot_markets)
     }
 
+    pub async fn load_markets(&self, reload: bool) -> Result<HashMap<String, Arc<Market>>> {
+        if !reload {
+            let markets = self.markets.read().map_err(|e| anyhow::anyhow!("{e}"))?;
+            if markets.loaded {
+                return Ok(markets.by_symbol.clone());
+            }
+        }
+
+        let mut markets = self.fetch_markets(&[]).await?;
+        if self.option.load_trading_fees {
+            let fees = self.fetch_raw_trading_fees().await?;
+            for market in &mut markets {
+                if let Some(fee) = fees.get(&trading_fee_key(market)) {
+                    let trading_fee = self.parse_trading_fee(fee, market);
+                    market.maker = trading_fee.maker;
+                    market.taker = trading_fee.taker;
+                }
+            }
+        }
+        let mut by_symbol = HashMap::new();
+        let mut by_id = HashMap::new();
+        for market in markets {
+            let market = Arc::new(market);
+            by_id.insert((category(&market).to_string(), market.id.clone()), market.clone());
+            by_symbol.insert(market.symbol.clone(), market);
+        }
+
+        let mut markets = self.markets.write().map_err(|e| anyhow::anyhow!("{e}"))?;
+        *markets = Markets {
+            loaded: true,
+            by_symbol: by_symbol.clone(),
+            by_id,
+        };
+        Ok(by_symbol)
+    }
+
+    // loads markets on the first call, unlike load_markets the cached markets are not copied
+    async fn ensure_markets(&self) -> Result<()> {
+        if self.markets.read().map_err(|e| anyhow::anyhow!("{e}"))?.loaded {
+            return Ok(());
+        }
+        self.load_markets(true).await?;
+        Ok(())
+    }
+
+    pub async fn fetch_trading_fee(&self, symbol: &str) -> Result<TradingFee> {
+        // https://bybit-exchange.github.io/docs/v5/account/fee-rate
+        self.ensure_markets().await?;
+        let market = self.market(symbol)?;
+        let category = category(&market);
+        // option fee rates are per base coin
+        let query = if market.option {
+            [("category", category), ("baseCoin", market.base_id.as_str())]
+        } else {
+            [("category", category), ("symbol", market.id.as_str())]
+        };
+        let result = self.private_get(self.api.fee_rate, &query).await?;
+        let fee = result.pointer("/list/0").with_context(|| format!("no fee rate of {symbol}"))?;
+        Ok(self.parse_trading_fee(fee, &market))
+    }
+
+    // symbol -> fee of all markets
+    pub async fn fetch_trading_fees(&self) -> Result<HashMap<String, TradingFee>> {
+        let markets = self.load_markets(false).await?;
+        let fees = self.fetch_raw_trading_fees().await?;
+        let mut res = HashMap::new();
+        for (symbol, market) in markets {
+            if let Some(fee) = fees.get(&trading_fee_key(&market)) {
+                res.insert(symbol, self.parse_trading_fee(fee, &market));
+            }
+        }
+        Ok(res)
+    }
+
+    // (category, symbol id or base coin of option) -> fee rate
+    async fn fetch_raw_trading_fees(&self) -> Result<HashMap<(String, String), Value>> {
+        // https://bybit-exchange.github.io/docs/v5/account/fee-rate
+        let (spot, linear, inverse, option) = tokio::try_join!(
+            self.private_get(self.api.fee_rate, &[("category", "spot")]),
+            self.private_get(self.api.fee_rate, &[("category", "linear")]),
+            self.private_get(self.api.fee_rate, &[("category", "inverse")]),
+            self.private_get(self.api.fee_rate, &[("category", "option")]),
+        )?;
+        let mut res = HashMap::new();
+        for (category, mut result) in [
+            ("spot", spot),
+            ("linear", linear),
+            ("inverse", inverse),
+            ("option", option),
+        ] {
+            let Some(list) = result.get_mut("list").and_then(|v| v.as_array_mut()) else {
+                continue;
+            };
+            let key = if category == "option" { "baseCoin" } else { "symbol" };
+            for fee in list.drain(..) {
+                if let Some(id) = fee.get(key).and_then(|v| v.as_str()).filter(|v| !v.is_empty()) {
+                    res.insert((category.to_string(), id.to_string()), fee);
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    fn parse_trading_fee(&self, fee: &Value, market: &Market) -> TradingFee {
+        TradingFee {
+            symbol: market.symbol.clone(),
+            maker: fee.get("makerFeeRate").and_then(|v| v.a_o_p_f64()),
+            taker: fee.get("takerFeeRate").and_then(|v| v.a_o_p_f64()),
+            percentage: Some(true),
+            tier_based: Some(true),
+            info: fee.clone(),
+        }
+    }
+
+    pub fn market(&self, symbol: &str) -> Result<Arc<Market>> {
+        let markets = self.markets.read().map_err(|e| anyhow::anyhow!("{e}"))?;
+        anyhow::ensure!(markets.loaded, "markets not loaded, call load_markets first");
+        markets.by_symbol.get(symbol).cloned().with_context(|| format!("market {symbol} not found"))
+    }
+
+    pub fn market_by_id(&self, id: &str, category: &str) -> Result<Arc<Market>> {
+        let markets = self.markets.read().map_err(|e| anyhow::anyhow!("{e}"))?;
+        anyhow::ensure!(markets.loaded, "markets not loaded, call load_markets first");
+        markets
+            .by_id
+            .get(&(category.to_string(), id.to_string()))
+            .cloned()
+            .with_context(|| format!("market {category} {id} not found"))
+    }
+
+    pub async fn fetch_ticker(&self, symbol: &str) -> Result<Ticker> {
+        // https://bybit-exchange.github.io/docs/v5/market/tickers
+        self.ensure_markets().await?;
+        let market = self.market(symbol)?;
+        let query = [("category", category(&market)), ("symbol", market.id.as_str())];
+        let (category, time, rows) = self.fetch_raw_tickers(&query).await?;
+        let row = rows.first().context("no ticker")?;
+        self.parse_ticker(row, &category, time).context("parse ticker fail")
+    }
+
+    pub async fn fetch_tickers(&self, symbols: &[String]) -> Result<HashMap<String, Ticker>> {
+        let mut res = HashMap::new();
+        for (category, time, rows) in self.fetch_raw_tickers_for_symbols(symbols).await? {
+            for row in &rows {
+                let Some(ticker) = self.parse_ticker(row, &category, time) else {
+                    continue;
+                };
+                if symbols.is_empty() || symbols.contains(&ticker.symbol) {
+                    res.insert(ticker.symbol.clone(), ticker);
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    // light version of fetch_tickers, only the top of the book is filled
+    pub async fn fetch_bids_asks(&self, symbols: &[String]) -> Result<HashMap<String, Ticker>> {
+        let mut res = HashMap::new();
+        for (category, time, rows) in self.fetch_raw_tickers_for_symbols(symbols).await? {
+            for row in &rows {
+                let Some(ticker) = self.parse_bid_ask(row, &category, time) else {
+                    continue;
+                };
+                if symbols.is_empty() || symbols.contains(&ticker.symbol) {
+                    res.insert(ticker.symbol.clone(), ticker);
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    // returns (category, time, list) of v5/market/tickers
+    async fn fetch_raw_tickers(&self, query: &[(&str, &str)]) -> Result<(String, i64, Vec<Value>)> {
+        let url = self.new_url(self.api.tickers)?;
+        let mut resp = self.http_client.get(url).query(query).send().await?.json::<Value>().await?;
+        self.check_resp(&resp)?;
+        let time = resp.get("time").and_then(|v| v.a_o_p_i64()).unwrap_or_else(timestamp);
+        let result = resp.get_mut("result").context("no result")?;
+        let category = result.get("category").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+        let list = result.get_mut("list").context("no list")?.as_array_mut().context("list not array")?;
+        Ok((category, time, std::mem::take(list)))
+    }
+
+    // option tickers can only be queried by base coin, others by category
+    async fn fetch_raw_tickers_for_symbols(&self, symbols: &[String]) -> Result<Vec<(String, i64, Vec<Value>)>> {
+        self.ensure_markets().await?;
+        let mut queries = Vec::new();
+        if symbols.is_empty() {
+            queries.extend(["spot", "linear", "inverse"].map(|category| vec![("category", category.to_string())]));
+            queries.extend(
+                ["BTC", "ETH", "SOL"]
+                    .map(|base| vec![("category", "option".to_string()), ("baseCoin", base.to_string())]),
+            );
+        }
+        for symbol in symbols {
+            let market = self.market(symbol)?;
+            let query = if market.option {
+                vec![("category", "option".to_string()), ("baseCoin", market.base_id.clone())]
+            } else {
+                vec![("category", category(&market).to_string())]
+            };
+            if !queries.contains(&query) {
+                queries.push(query);
+            }
+        }
+
+        let futures = queries.iter().map(|query| {
+            let query = query.iter().map(|(k, v)| (*k, v.as_str())).collect::<Vec<_>>();
+            async move { self.fetch_raw_tickers(&query).await }
+        });
+        futures::future::join_all(futures).await.into_iter().collect()
+    }
+
+    fn parse_ticker(&self, ticker: &Value, category: &str, time: i64) -> Option<Ticker> {
+        let id = ticker.get("symbol").and_then(|v| v.as_str())?;
+        let market = self.market_by_id(id, category).ok()?;
+        let last = ticker.get("lastPrice").and_then(|v| v.a_o_p_f64());
+        // spot and futures have price24hPcnt, options have change24h, both are ratio
+        let percentage =
+            ticker.get("price24hPcnt").or(ticker.get("change24h")).and_then(|v| v.a_o_p_f64()).map(|v| v * 100.0);
+        let ticker = Ticker {
+            symbol: market.symbol.clone(),
+            timestamp: Some(time),
+            datetime: iso_8601(time),
+            high: ticker.get("highPrice24h").and_then(|v| v.a_o_p_f64()),
+            low: ticker.get("lowPrice24h").and_then(|v| v.a_o_p_f64()),
+            bid: ticker.get("bid1Price").and_then(|v| v.a_o_p_f64()),
+            bid_volume: ticker.get("bid1Size").and_then(|v| v.a_o_p_f64()),
+            ask: ticker.get("ask1Price").and_then(|v| v.a_o_p_f64()),
+            ask_volume: ticker.get("ask1Size").and_then(|v| v.a_o_p_f64()),
+            vwap: None,
+            open: ticker.get("prevPrice24h").and_then(|v| v.a_o_p_f64()),
+            close: last,
+            last,
+            previous_close: None,
+            change: None,
+            percentage,
+            average: None,
+            base_volume: ticker.get("volume24h").and_then(|v| v.a_o_p_f64()),
+            quote_volume: ticker.get("turnover24h").and_then(|v| v.a_o_p_f64()),
+            mark_price: ticker.get("markPrice").and_then(|v| v.a_o_p_f64()),
+            index_price: ticker.get("indexPrice").and_then(|v| v.a_o_p_f64()),
+            info: ticker.as_object().map(|v| v.clone().into_iter().collect()).unwrap_or_default(),
+        };
+        Some(ticker.derive())
+    }
+
+    fn parse_bid_ask(&self, ticker: &Value, category: &str, time: i64) -> Option<Ticker> {
+        let id = ticker.get("symbol").and_then(|v| v.as_str())?;
+        let market = self.market_by_id(id, category).ok()?;
+        Some(Ticker {
+            symbol: market.symbol.clone(),
+            timestamp: Some(time),
+            datetime: iso_8601(time),
+            bid: ticker.get("bid1Price").and_then(|v| v.a_o_p_f64()),
+            bid_volume: ticker.get("bid1Size").and_then(|v| v.a_o_p_f64()),
+            ask: ticker.get("ask1Price").and_then(|v| v.a_o_p_f64()),
+            ask_volume: ticker.get("ask1Size").and_then(|v| v.a_o_p_f64()),
+            info: ticker.as_object().map(|v| v.clone().into_iter().collect()).unwrap_or_default(),
+            ..Default::default()
+        })
+    }
+
+    // without symbols, funding rates of all linear and inverse contracts are fetched
+    pub async fn fetch_funding_rates(&self, symbols: &[String]) -> Result<HashMap<String, FundingRate>> {
+        // https://bybit-exchange.github.io/docs/v5/market/tickers
+        self.ensure_markets().await?;
+        let raw_tickers = if symbols.is_empty() {
+            let (linear, inverse) = tokio::join!(
+                self.fetch_raw_tickers(&[("category", "linear")]),
+                self.fetch_raw_tickers(&[("category", "inverse")])
+            );
+            vec![linear?, inverse?]
+        } else {
+            for symbol in symbols {
+                let market = self.market(symbol)?;
+                anyhow::ensure!(market.contract && !market.option, "{symbol} is not linear or inverse");
+            }
+            self.fetch_raw_tickers_for_symbols(symbols).await?
+        };
+
+        let mut res = HashMap::new();
+        for (category, time, rows) in raw_tickers {
+            for row in &rows {
+                let Some(funding_rate) = self.parse_funding_rate(row, &category, time) else {
+                    continue;
+                };
+                if symbols.is_empty() || symbols.contains(&funding_rate.symbol) {
+                    res.insert(funding_rate.symbol.clone(), funding_rate);
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    // pages backward with endTime until since is covered or limit entries are collected,
+    // without both only the latest page is fetched
+    // with since, endTime is anchored at since plus limit funding intervals and the earliest entries are kept,
+    // otherwise the latest ones
+    pub async fn fetch_funding_rate_history(
+        &self,
+        symbol: &str,
+        since: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<FundingRateHistory>> {
+        // https://bybit-exchange.github.io/docs/v5/market/history-fund-rate
+        const MAX_LIMIT: i64 = 200;
+        self.ensure_markets().await?;
+        let market = self.market(symbol)?;
+        anyhow::ensure!(market.swap, "{symbol} is not a perpetual contract");
+        let url = self.new_url(self.api.funding_rate_history)?;
+        let query = [("category", category(&market)), ("symbol", market.id.as_str())];
+        // fundingInterval of instrument in minute
+        let interval = market.info.get("fundingInterval").and_then(|v| v.a_o_p_i64()).unwrap_or(480) * 60 * 1000;
+        let mut end = match (since, limit) {
+            (Some(since), Some(limit)) => (since + limit * interval - 1).min(timestamp()),
+            _ => timestamp(),
+        };
+
+        let mut res = Vec::new();
+        loop {
+            let page_limit = match (since, limit) {
+                (None, Some(limit)) => (limit - res.len() as i64).clamp(1, MAX_LIMIT),
+                _ => MAX_LIMIT,
+            };
+            let mut page_query = query.map(|(k, v)| (k, v.to_string())).to_vec();
+            page_query.extend([("endTime", end.to_string()), ("limit", page_limit.to_string())]);
+            let resp = self.http_client.get(url.clone()).query(&page_query).send().await?.json::<Value>().await?;
+            self.check_resp(&resp)?;
+            let list = resp.pointer("/result/list").and_then(|v| v.as_array()).context("no list")?;
+            let page = list
+                .iter()
+                .filter_map(|v| {
+                    let timestamp = v.get("fundingRateTimestamp").and_then(|v| v.a_o_p_i64())?;
+                    Some(FundingRateHistory {
+                        symbol: market.symbol.clone(),
+                        funding_rate: v.get("fundingRate").and_then(|v| v.a_o_p_f64())?,
+                        timestamp,
+                        datetime: iso_8601(timestamp),
+                        info: v.clone(),
+                    })
+                })
+                .collect::<Vec<_>>();
+            let Some(oldest) = page.iter().map(|v| v.timestamp).min() else {
+                break;
+            };
+            let page_len = page.len() as i64;
+            res.extend(page);
+            if page_len < page_limit
+                || (since.is_none() && limit.is_none())
+                || since.is_some_and(|since| oldest <= since)
+                || (since.is_none() && limit.is_some_and(|limit| res.len() as i64 >= limit))
+            {
+                break;
+            }
+            end = oldest - 1;
+        }
+
+        res.sort_by_key(|v| v.timestamp);
+        res.dedup_by_key(|v| v.timestamp);
+        if let Some(since) = since {
+            res.retain(|v| v.timestamp >= since);
+        }
+        filter_by_limit(&mut res, limit, since.is_some());
+        Ok(res)
+    }
+
+    fn parse_funding_rate(&self, ticker: &Value, category: &str, time: i64) -> Option<FundingRate> {
+        let id = ticker.get("symbol").and_then(|v| v.as_str())?;
+        let market = self.market_by_id(id, category).ok()?;
+        let funding_timestamp = ticker.get("nextFundingTime").and_then(|v| v.a_o_p_i64()).filter(|v| *v != 0);
+        // fundingIntervalHour of ticker, or fundingInterval in minute of instrument
+        let interval = ticker
+            .get("fundingIntervalHour")
+            .and_then(|v| v.a_o_p_i64())
+            .or(market.info.get("fundingInterval").and_then(|v| v.a_o_p_i64()).map(|v| v / 60))
+            .map(|v| format!("{v}h"));
+        Some(FundingRate {
+            symbol: market.symbol.clone(),
+            mark_price: ticker.get("markPrice").and_then(|v| v.a_o_p_f64()),
+            index_price: ticker.get("indexPrice").and_then(|v| v.a_o_p_f64()),
+            funding_rate: ticker.get("fundingRate").and_then(|v| v.a_o_p_f64()),
+            funding_timestamp,
+            funding_datetime: funding_timestamp.and_then(iso_8601),
+            interval,
+            timestamp: Some(time),
+            datetime: iso_8601(time),
+            info: ticker.clone(),
+        })
+    }
+
+    pub async fn fetch_open_interest(&self, symbol: &str) -> Result<OpenInterest> {
+        let open_interest = self.fetch_open_interest_history(symbol, "5m", None, None, Some(1)).await?;
+        open_interest.into_iter().next().with_context(|| format!("open interest of {symbol} not found"))
+    }
+
+    // timeframe: 5m, 15m, 30m, 1h, 4h, 1d
+    // pages backward from until, the latest ones are kept when limit is reached
+    pub async fn fetch_open_interest_history(
+        &self,
+        symbol: &str,
+        timeframe: &str,
+        since: Option<i64>,
+        until: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<OpenInterest>> {
+        // https://bybit-exchange.github.io/docs/v5/market/open-interest
+        self.ensure_markets().await?;
+        let market = self.market(symbol)?;
+        anyhow::ensure!(market.contract && !market.option, "{symbol} is not linear or inverse");
+        let interval = interval_time(timeframe).with_context(|| format!("unsupported timeframe {timeframe}"))?;
+        let mut query = vec![
+            ("category", category(&market).to_string()),
+            ("symbol", market.id.clone()),
+            ("intervalTime", interval.to_string()),
+        ];
+        query.extend(since.map(|v| ("startTime", v.to_string())));
+        query.extend(until.map(|v| ("endTime", v.to_string())));
+        let list = self.fetch_paged(PagedApi::public(self.api.open_interest, 200), &query, None, None, limit).await?;
+        let mut res = list
+            .iter()
+            .filter_map(|v| {
+                let timestamp = v.get("timestamp").and_then(|v| v.a_o_p_i64())?;
+                let open_interest = v.get("openInterest").and_then(|v| v.a_o_p_f64());
+                Some(OpenInterest {
+                    symbol: market.symbol.clone(),
+                    // contracts of linear are in base currency, inverse are in usd
+                    open_interest_amount: open_interest.filter(|_| market.linear == Some(true)),
+                    open_interest_value: open_interest.filter(|_| market.inverse == Some(true)),
+                    timestamp,
+                    datetime: iso_8601(timestamp),
+                    info: v.clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+        res.sort_by_key(|v| v.timestamp);
+        Ok(res)
+    }
+
+    // timeframe: 5m, 15m, 30m, 1h, 4h, 1d
+    // pages backward from until, the latest ones are kept when limit is reached
+    pub async fn fetch_long_short_ratio_history(
+        &self,
+        symbol: &str,
+        timeframe: &str,
+        since: Option<i64>,
+        until: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<LongShortRatio>> {
+        // https://bybit-exchange.github.io/docs/v5/market/long-short-ratio
+        self.ensure_markets().await?;
+        let market = self.market(symbol)?;
+        anyhow::ensure!(market.contract && !market.option, "{symbol} is not linear or inverse");
+        let period = interval_time(timeframe).with_context(|| format!("unsupported timeframe {timeframe}"))?;
+        let mut query = vec![
+            ("category", category(&market).to_string()),
+            ("symbol", market.id.clone()),
+            ("period", period.to_string()),
+        ];
+        query.extend(since.map(|v| ("startTime", v.to_string())));
+        query.extend(until.map(|v| ("endTime", v.to_string())));
+        let list = self
+            .fetch_paged(
+                PagedApi::public(self.api.long_short_ratio, 500),
+                &query,
+                None,
+                None,
+                limit,
+            )
+            .await?;
+        let mut res = list
+            .iter()
+            .filter_map(|v| {
+                let timestamp = v.get("timestamp").and_then(|v| v.a_o_p_i64())?;
+                let buy_ratio = v.get("buyRatio").and_then(|v| v.a_o_p_f64())?;
+                let sell_ratio = v.get("sellRatio").and_then(|v| v.a_o_p_f64())?;
+                Some(LongShortRatio {
+                    symbol: market.symbol.clone(),
+                    timeframe: timeframe.to_string(),
+                    long_short_ratio: if sell_ratio == 0.0 {
+                        None
+                    } else {
+                        Some(buy_ratio / sell_ratio)
+                    },
+                    timestamp,
+                    datetime: iso_8601(timestamp),
+                    info: v.clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+        res.sort_by_key(|v| v.timestamp);
+        Ok(res)
+    }
+
+    // historical volatility of option underlying
+    // period: days of 7, 14, 21, 30, 60, 90, 180, 270, default 7
+    // without since, the data of the latest hour is returned, otherwise [since, now] is split into windows of 30 days
+    pub async fn fetch_volatility_history(
+        &self,
+        code: &str,
+        period: Option<i64>,
+        since: Option<i64>,
+    ) -> Result<Vec<Volatility>> {
+        // https://bybit-exchange.github.io/docs/v5/market/iv
+        const WINDOW: i64 = 30 * DAY;
+        let period = period.unwrap_or(7).to_string();
+        let query = [("category", "option"), ("baseCoin", code), ("period", period.as_str())];
+        let windows = match since {
+            Some(since) => {
+                let now = timestamp();
+                let mut windows = Vec::new();
+                let mut start = since;
+                while start <= now {
+                    let end = (start + WINDOW - 1).min(now);
+                    windows.push(Some((start.to_string(), end.to_string())));
+                    start = end + 1;
+                }
+                windows
+            }
+            None => vec![None],
+        };
+
+        let mut res = Vec::new();
+        for window in windows {
+            let mut page_query = query.to_vec();
+            if let Some((start, end)) = window.as_ref() {
+                page_query.extend([("startTime", start.as_str()), ("endTime", end.as_str())]);
+            }
+            let result = self.public_get(self.api.volatility_history, &page_query).await?;
+            let list = result.as_array().context("result not array")?;
+            res.extend(list.iter().filter_map(|v| {
+                let timestamp = v.get("time").and_then(|v| v.a_o_p_i64())?;
+                Some(Volatility {
+                    timestamp,
+                    datetime: iso_8601(timestamp),
+                    volatility: v.get("value").and_then(|v| v.a_o_p_f64())?,
+                    info: v.clone(),
+                })
+            }));
+        }
+        res.sort_by_key(|v| v.timestamp);
+        res.dedup_by_key(|v| v.timestamp);
+        Ok(res)
+    }
+
+    pub async fn fetch_market_leverage_tiers(&self, symbol: &str) -> Result<Vec<LeverageTier>> {
+        let mut tiers = self.fetch_leverage_tiers(&[symbol.to_string()]).await?;
+        tiers.remove(symbol).with_context(|| format!("no leverage tiers of {symbol}"))
+    }
+
+    // symbol -> tiers sorted by notional, all linear and inverse markets when symbols is empty
+    pub async fn fetch_leverage_tiers(&self, symbols: &[String]) -> Result<HashMap<String, Vec<LeverageTier>>> {
+        // https://bybit-exchange.github.io/docs/v5/market/risk-limit
+        self.ensure_markets().await?;
+        let mut queries = Vec::new();
+        if symbols.is_empty() {
+            queries.push(vec![("category", "linear".to_string())]);
+            queries.push(vec![("category", "inverse".to_string())]);
+        } else {
+            for symbol in symbols {
+                let market = self.market(symbol)?;
+                anyhow::ensure!(market.contract && !market.option, "{symbol} is not linear or inverse");
+                let query = vec![
+                    ("category", category(&market).to_string()),
+                    ("symbol", market.id.clone()),
+                ];
+                queries.push(query);
+            }
+        }
+
+        let mut rows_by_market: HashMap<String, (Arc<Market>, Vec<Value>)> = HashMap::new();
+        for query in queries {
+            let category = query[0].1.clone();
+            for row in self.fetch_paged(PagedApi::public(self.api.risk_limit, 200), &query, None, None, None).await? {
+                let Some(market) =
+                    row.get("symbol").and_then(|v| v.as_str()).and_then(|v| self.market_by_id(v, &category).ok())
+                else {
+                    continue;
+                };
+                if symbols.is_empty() || symbols.contains(&market.symbol) {
+                    rows_by_market.entry(market.symbol.clone()).or_insert((market, Vec::new())).1.push(row);
+                }
+            }
+        }
+
+        let mut res = HashMap::new();
+        for (symbol, (market, rows)) in rows_by_market {
+            res.insert(symbol, self.parse_market_leverage_tiers(&rows, &market));
+        }
+        Ok(res)
+    }
+
+    // each row is the upper bound of a tier, the lower bound is the upper bound of the previous tier
+    fn parse_market_leverage_tiers(&self, rows: &[Value], market: &Market) -> Vec<LeverageTier> {
+        let get_f64 = |row: &Value, key: &str| row.get(key).and_then(|v| v.a_o_p_f64());
+        let mut rows = rows.iter().filter(|v| get_f64(v, "riskLimitValue").is_some()).collect::<Vec<_>>();
+        rows.sort_by(|a, b| {
+            let (a, b) = (get_f64(a, "riskLimitValue"), get_f64(b, "riskLimitValue"));
+            a.unwrap_or_default().total_cmp(&b.unwrap_or_default())
+        });
+        let mut res = Vec::new();
+        let mut min_notional = 0.0;
+        for (i, row) in rows.into_iter().enumerate() {
+            let max_notional = get_f64(row, "riskLimitValue").unwrap_or_default();
+            res.push(LeverageTier {
+                tier: i as i64 + 1,
+                symbol: market.symbol.clone(),
+                currency: market.settle.clone(),
+                min_notional,
+                max_notional,
+                maintenance_margin_rate: get_f64(row, "maintenanceMargin"),
+                initial_margin_rate: get_f64(row, "initialMargin"),
+                maintenance_margin_deduction: get_f64(row, "mmDeduction"),
+                max_leverage: get_f64(row, "maxLeverage"),
+                info: row.clone(),
+            });
+            min_notional = max_notional;
+        }
+        res
+    }
+
+    pub async fn fetch_greeks(&self, symbol: &str) -> Result<Greeks> {
+        let mut greeks = self.fetch_all_greeks(&[symbol.to_string()]).await?;
+        greeks.remove(symbol).with_context(|| format!("greeks of {symbol} not found"))
+    }
+
+    // without symbols, greeks of all BTC, ETH and SOL options are fetched
+    pub async fn fetch_all_greeks(&self, symbols: &[String]) -> Result<HashMap<String, Greeks>> {
+        // https://bybit-exchange.github.io/docs/v5/market/tickers
+        let mut res = HashMap::new();
+        for (time, rows) in self.fetch_option_tickers(symbols).await? {
+            for row in &rows {
+                let Some(greeks) = self.parse_greeks(row, time) else {
+                    continue;
+                };
+                if symbols.is_empty() || symbols.contains(&greeks.symbol) {
+                    res.insert(greeks.symbol.clone(), greeks);
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    pub async fn fetch_option(&self, symbol: &str) -> Result<OptionContract> {
+        let (time, rows) = self.fetch_option_tickers(&[symbol.to_string()]).await?.pop().context("no ticker")?;
+        rows.iter()
+            .filter_map(|row| self.parse_option_contract(row, time))
+            .find(|v| v.symbol == symbol)
+            .with_context(|| format!("option {symbol} not found"))
+    }
+
+    // every strike and expiry of underlying like BTC, keyed by symbol
+    pub async fn fetch_option_chain(&self, underlying: &str) -> Result<HashMap<String, OptionContract>> {
+        // https://bybit-exchange.github.io/docs/v5/market/tickers
+        self.ensure_markets().await?;
+        let (_, time, rows) = self.fetch_raw_tickers(&[("category", "option"), ("baseCoin", underlying)]).await?;
+        let res = rows
+            .iter()
+            .filter_map(|row| self.parse_option_contract(row, time))
+            .map(|v| (v.symbol.clone(), v))
+            .collect();
+        Ok(res)
+    }
+
+    // (time, list) of option tickers of base coins of symbols
+    async fn fetch_option_tickers(&self, symbols: &[String]) -> Result<Vec<(i64, Vec<Value>)>> {
+        self.ensure_markets().await?;
+        let mut bases = Vec::new();
+        for symbol in symbols {
+            let market = self.market(symbol)?;
+            anyhow::ensure!(market.option, "{symbol} is not an option");
+            if !bases.contains(&market.base_id) {
+                bases.push(market.base_id.clone());
+            }
+        }
+        if symbols.is_empty() {
+            bases = ["BTC", "ETH", "SOL"].map(|v| v.to_string()).to_vec();
+        }
+        let futures = bases.iter().map(|base| async move {
+            let (_, time, rows) = self.fetch_raw_tickers(&[("category", "option"), ("baseCoin", base)]).await?;
+            anyhow::Ok((time, rows))
+        });
+        futures::future::try_join_all(futures).await
+    }
+
+    fn parse_greeks(&self, ticker: &Value, time: i64) -> Option<Greeks> {
+        let market = self.market_by_id(ticker.get("symbol").and_then(|v| v.as_str())?, "option").ok()?;
+        let get_f64 = |key: &str| ticker.get(key).and_then(|v| v.a_o_p_f64());
+        Some(Greeks {
+            symbol: market.symbol.clone(),
+            timestamp: Some(time),
+            datetime: iso_8601(time),
+            delta: get_f64("delta"),
+            gamma: get_f64("gamma"),
+            theta: get_f64("theta"),
+            vega: get_f64("vega"),
+            rho: None,
+            bid_size: get_f64("bid1Size"),
+            ask_size: get_f64("ask1Size"),
+            bid_implied_volatility: get_f64("bid1Iv"),
+            ask_implied_volatility: get_f64("ask1Iv"),
+            mark_implied_volatility: get_f64("markIv"),
+            bid_price: get_f64("bid1Price"),
+            ask_price: get_f64("ask1Price"),
+            mark_price: get_f64("markPrice"),
+            last_price: get_f64("lastPrice"),
+            underlying_price: get_f64("underlyingPrice"),
+            info: ticker.clone(),
+        })
+    }
+
+    fn parse_option_contract(&self, ticker: &Value, time: i64) -> Option<OptionContract> {
+        let market = self.market_by_id(ticker.get("symbol").and_then(|v| v.as_str())?, "option").ok()?;
+        let get_f64 = |key: &str| ticker.get(key).and_then(|v| v.a_o_p_f64());
+        let bid_price = get_f64("bid1Price").filter(|v| *v != 0.0);
+        let ask_price = get_f64("ask1Price").filter(|v| *v != 0.0);
+        Some(OptionContract {
+            symbol: market.symbol.clone(),
+            currency: market.base.clone(),
+            strike: market.strike,
+            expiry: market.expiry,
+            option_type: market.option_type.clone(),
+            timestamp: Some(time),
+            datetime: iso_8601(time),
+            implied_volatility: get_f64("markIv"),
+            open_interest: get_f64("openInterest"),
+            bid_price,
+            ask_price,
+            mid_price: bid_price.zip(ask_price).map(|(bid, ask)| (bid + ask) / 2.0),
+            mark_price: get_f64("markPrice"),
+            last_price: get_f64("lastPrice"),
+            underlying_price: get_f64("underlyingPrice"),
+            // change24h is ratio
+            percentage: get_f64("change24h").map(|v| v * 100.0),
+            base_volume: get_f64("volume24h"),
+            quote_volume: get_f64("turnover24h"),
+            info: ticker.clone(),
+        })
+    }
+
+    pub async fn fetch_order_book(&self, symbol: &str, limit: Option<i64>) -> Result<OrderBook> {
+        // https://bybit-exchange.github.io/docs/v5/market/orderbook
+        self.ensure_markets().await?;
+        let market = self.market(symbol)?;
+        let category = category(&market);
+        let max_limit = match category {
+            "spot" => 200,
+            "option" => 25,
+            _ => 500,
+        };
+        let limit = limit.map_or(max_limit, |v| v.clamp(1, max_limit)).to_string();
+        let query = [
+            ("category", category),
+            ("symbol", market.id.as_str()),
+            ("limit", limit.as_str()),
+        ];
+        let url = self.new_url(self.api.order_book)?;
+        let mut resp = self.http_client.get(url).query(&query).send().await?.json::<Value>().await?;
+        self.check_resp(&resp)?;
+        let result = resp.get_mut("result").context("no result")?.take();
+        let parse_side = |key: &str| {
+            result
+                .get(key)
+                .and_then(|v| v.as_array())
+                .map(|levels| {
+                    levels
+                        .iter()
+                        .filter_map(|level| Some((level.get(0)?.a_o_p_f64()?, level.get(1)?.a_o_p_f64()?)))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default()
+        };
+        let timestamp = result.get("ts").and_then(|v| v.a_o_p_i64());
+        let mut order_book = OrderBook {
+            symbol: market.symbol.clone(),
+            bids: parse_side("b"),
+            asks: parse_side("a"),
+            timestamp,
+            datetime: timestamp.and_then(iso_8601),
+            nonce: result.get("u").or(result.get("seq")).and_then(|v| v.a_o_p_i64()),
+            info: Value::Null,
+        };
+        order_book.sort();
+        order_book.info = result;
+        Ok(order_book)
+    }
+
+    pub async fn fetch_trades(&self, symbol: &str, since: Option<i64>, limit: Option<i64>) -> Result<Vec<Trade>> {
+        // https://bybit-exchange.github.io/docs/v5/market/recent-trade
+        // only the most recent trades are available, since is applied on the result
+        self.ensure_markets().await?;
+        let market = self.market(symbol)?;
+        let category = category(&market);
+        let max_limit = if category == "spot" { 60 } else { 1000 };
+        let limit = limit.map_or(max_limit, |v| v.clamp(1, max_limit)).to_string();
+        let query = [
+            ("category", category),
+            ("symbol", market.id.as_str()),
+            ("limit", limit.as_str()),
+        ];
+        let url = self.new_url(self.api.recent_trade)?;
+        let resp = self.http_client.get(url).query(&query).send().await?.json::<Value>().await?;
+        self.check_resp(&resp)?;
+        let result = resp.get("result").context("no result")?;
+        let list = result.get("list").context("no list")?.as_array().context("list not array")?;
+
+        let mut res = Vec::new();
+        for trade in list {
+            let Some(id) = trade.get("execId").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let timestamp = trade.get("time").and_then(|v| v.a_o_p_i64());
+            if since.is_some_and(|since| timestamp.is_some_and(|ts| ts < since)) {
+                continue;
+            }
+            let price = trade.get("price").and_then(|v| v.a_o_p_f64());
+            let amount = trade.get("size").and_then(|v| v.a_o_p_f64());
+            // inverse contracts are quoted in usd, the cost is in base currency
+            let contract_size = market.contract_size.unwrap_or(1.0);
+            let cost = price.zip(amount).filter(|(p, _)| *p > 0.0).map(|(p, a)| {
+                if market.inverse == Some(true) {
+                    a * contract_size / p
+                } else {
+                    p * a * contract_size
+                }
+            });
+            res.push(Trade {
+                id: id.to_string(),
+                order: None,
+                timestamp,
+                datetime: timestamp.and_then(iso_8601),
+                symbol: market.symbol.clone(),
+                r#type: None,
+                side: trade.get("side").and_then(|v| v.as_str()).map(|v| v.to_lowercase()),
+                taker_or_maker: None,
+                price,
+                amount,
+                cost,
+                fee: None,
+                info: trade.clone(),
+            });
+        }
+        res.sort_by_key(|v| v.timestamp);
+        Ok(res)
+    }
+
+    pub async fn fetch_ohlcv(
+        &self,
+        symbol: &str,
+        timeframe: &str,
+        since: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<OHLCV>> {
+        // https://bybit-exchange.github.io/docs/v5/market/kline
+        // at most 1000 candles per request, pages backward with end until since or limit is covered
+        const MAX_LIMIT: i64 = 1000;
+        self.ensure_markets().await?;
+        let market = self.market(symbol)?;
+        let (interval, duration) =
+            super::timeframe(timeframe).with_context(|| format!("unknown timeframe {timeframe}"))?;
+        let limit = match (since, limit) {
+            (_, Some(limit)) => Some(limit.max(1)),
+            (Some(_), None) => None,
+            (None, None) => Some(200),
+        };
+        let mut end = match (since, limit) {
+            (Some(since), Some(limit)) => (since + limit * duration - 1).min(timestamp()),
+            _ => timestamp(),
+        };
+        let url = self.new_url(self.api.kline)?;
+        let query = [
+            ("category", category(&market)),
+            ("symbol", market.id.as_str()),
+            ("interval", interval),
+        ];
+
+        let mut res = Vec::new();
+        loop {
+            let page_limit = limit.map_or(MAX_LIMIT, |limit| (limit - res.len() as i64).min(MAX_LIMIT));
+            let mut page_query = query.map(|(k, v)| (k, v.to_string())).to_vec();
+            page_query.extend([("end", end.to_string()), ("limit", page_limit.to_string())]);
+            if let Some(since) = since {
+                page_query.push(("start", since.to_string()));
+            }
+            let resp = self.http_client.get(url.clone()).query(&page_query).send().await?.json::<Value>().await?;
+            self.check_resp(&resp)?;
+            let result = resp.get("result").context("no result")?;
+            let list = result.get("list").context("no list")?.as_array().context("list not array")?;
+            let candles = list
+                .iter()
+                .filter_map(|v| {
+                    Some(OHLCV {
+                        timestamp: v.get(0)?.a_o_p_i64()?,
+                        open: v.get(1)?.a_o_p_f64()?,
+                        high: v.get(2)?.a_o_p_f64()?,
+                        low: v.get(3)?.a_o_p_f64()?,
+                        close: v.get(4)?.a_o_p_f64()?,
+                        volume: v.get(5)?.a_o_p_f64()?,
+                    })
+                })
+                .collect::<Vec<_>>();
+            let Some(oldest) = candles.iter().map(|v| v.timestamp).min() else {
+                break;
+            };
+            let page_len = candles.len() as i64;
+            res.extend(candles);
+            if page_len < page_limit
+                || since.is_some_and(|since| oldest <= since)
+                || limit.is_some_and(|limit| res.len() as i64 >= limit)
+            {
+                break;
+            }
+            end = oldest - 1;
+        }
+
+        res.sort_by_key(|v| v.timestamp);
+        res.dedup_by_key(|v| v.timestamp);
+        if let Some(since) = since {
+            res.retain(|v| v.timestamp >= since);
+        }
+        filter_by_limit(&mut res, limit, since.is_some());
+        Ok(res)
+    }
+
+    pub async fn create_order(&self, request: &OrderRequest) -> Result<Order> {
+        // https://bybit-exchange.github.io/docs/v5/order/create-order
+        self.ensure_markets().await?;
+        let market = self.market(&request.symbol)?;
+        let mut body = self.order_request_body(request, &market)?;
+        body.insert("category".to_string(), category(&market).into());
+        let result = self.private_post(self.api.create_order, &Value::Object(body)).await?;
+        Ok(self.fill_order_from_request(self.parse_order(&result, &market), request))
+    }
+
+    // all orders must be in the same category, they are sent in batches of batch_limit
+    pub async fn create_orders(&self, requests: &[OrderRequest]) -> Result<Vec<Order>> {
+        // https://bybit-exchange.github.io/docs/v5/order/batch-place
+        self.ensure_markets().await?;
+        let mut markets = Vec::new();
+        let mut bodies = Vec::new();
+        for request in requests {
+            let market = self.market(&request.symbol)?;
+            bodies.push(Value::Object(self.order_request_body(request, &market)?));
+            markets.push(market);
+        }
+        let category = self.same_category(&markets)?;
+        let orders = self.post_batch_orders(self.api.create_orders, category, bodies, &markets).await;
+        let res = orders
+            .into_iter()
+            .zip(requests)
+            .map(|(mut order, request)| {
+                if order.status.as_deref() == Some("rejected") {
+                    order.client_order_id = request.client_order_id.clone();
+                    order
+                } else {
+                    self.fill_order_from_request(order, request)
+                }
+            })
+            .collect();
+        Ok(res)
+    }
+
+    pub async fn create_market_buy_order_with_cost(&self, symbol: &str, cost: f64) -> Result<Order> {
+        self.create_order(&OrderRequest::new(symbol, "market", "buy", 0.0).cost(cost)).await
+    }
+
+    pub async fn create_market_sell_order_with_cost(&self, symbol: &str, cost: f64) -> Result<Order> {
+        self.create_order(&OrderRequest::new(symbol, "market", "sell", 0.0).cost(cost)).await
+    }
+
+    // amount 0 keeps the original quantity
+    pub async fn edit_order(&self, id: &str, request: &OrderRequest) -> Result<Order> {
+        // https://bybit-exchange.github.io/docs/v5/order/amend-order
+        self.ensure_markets().await?;
+        let market = self.market(&request.symbol)?;
+        let mut body = self.amend_request_body(id, request, &market);
+        body.insert("category".to_string(), category(&market).into());
+        let result = self.private_post(self.api.edit_order, &Value::Object(body)).await?;
+        Ok(self.fill_order_from_request(self.parse_order(&result, &market), request))
+    }
+
+    // (id, request), all orders must be in the same category, they are sent in batches of batch_limit
+    pub async fn edit_orders(&self, orders: &[(String, OrderRequest)]) -> Result<Vec<Order>> {
+        // https://bybit-exchange.github.io/docs/v5/order/batch-amend
+        self.ensure_markets().await?;
+        let mut markets = Vec::new();
+        let mut bodies = Vec::new();
+        for (id, request) in orders {
+            let market = self.market(&request.symbol)?;
+            bodies.push(Value::Object(self.amend_request_body(id, request, &market)));
+            markets.push(market);
+        }
+        let category = self.same_category(&markets)?;
+        let edited = self.post_batch_orders(self.api.edit_orders, category, bodies, &markets).await;
+        let orders = edited.into_iter().zip(orders);
+        let res = orders
+            .map(|(order, (_, request))| {
+                if order.status.as_deref() == Some("rejected") {
+                    order
+                } else {
+                    self.fill_order_from_request(order, request)
+                }
+            })
+            .collect();
+        Ok(res)
+    }
+
+    pub async fn cancel_order(&self, id: &str, symbol: &str) -> Result<Order> {
+        // https://bybit-exchange.github.io/docs/v5/order/cancel-order
+        self.ensure_markets().await?;
+        let market = self.market(symbol)?;
+        let body = serde_json::json!({ "category": category(&market), "symbol": market.id, "orderId": id });
+        let result = self.private_post(self.api.cancel_order, &body).await?;
+        Ok(self.parse_order(&result, &market))
+    }
+
+    pub async fn cancel_orders(&self, ids: &[String], symbol: &str) -> Result<Vec<Order>> {
+        let orders = ids.iter().map(|id| (symbol.to_string(), id.clone())).collect::<Vec<_>>();
+        self.cancel_orders_for_symbols(&orders).await
+    }
+
+    // (symbol, id), orders of each category are sent in batches of batch_limit
+    // orders are returned in the order of input, the orders of a failed batch are rejected
+    pub async fn cancel_orders_for_symbols(&self, orders: &[(String, String)]) -> Result<Vec<Order>> {
+        // https://bybit-exchange.github.io/docs/v5/order/batch-cancel
+        self.ensure_markets().await?;
+        let markets = orders.iter().map(|(symbol, _)| self.market(symbol)).collect::<Result<Vec<_>>>()?;
+        // category -> indexes of orders
+        let mut by_category: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (i, market) in markets.iter().enumerate() {
+            by_category.entry(category(market)).or_default().push(i);
+        }
+
+        let futures = by_category.iter().map(|(category, indexes)| {
+            let category_markets = indexes.iter().map(|i| markets[*i].clone()).collect::<Vec<_>>();
+            let bodies = indexes
+                .iter()
+                .zip(&category_markets)
+                .map(|(i, market)| serde_json::json!({ "symbol": market.id, "orderId": orders[*i].1 }))
+                .collect::<Vec<_>>();
+            async move {
+                let canceled =
+                    self.post_batch_orders(self.api.cancel_orders, category, bodies, &category_markets).await;
+                indexes.iter().copied().zip(canceled).collect::<Vec<_>>()
+            }
+        });
+        let mut res = futures::future::join_all(futures).await.into_iter().flatten().collect::<Vec<_>>();
+        res.sort_by_key(|(i, _)| *i);
+        // rejected orders carry no orderId in the response
+        let res = res.into_iter().map(|(i, mut order)| {
+            if order.id.is_empty() {
+                order.id = orders[i].1.clone();
+            }
+            order
+        });
+        Ok(res.collect())
+    }
+
+    // without symbol, orders of every scope of order_scopes are canceled
+    pub async fn cancel_all_orders(&self, symbol: Option<&str>) -> Result<Vec<Order>> {
+        // https://bybit-exchange.github.io/docs/v5/order/cancel-all
+        let queries = self
+            .order_scopes(symbol)
+            .await?
+            .into_iter()
+            .map(|scope| scope.into_iter().map(|(k, v)| (k.to_string(), Value::from(v))).collect::<Value>())
+            .collect::<Vec<_>>();
+
+        let futures = queries.iter().map(|body| async move {
+            let result = self.private_post(self.api.cancel_all_orders, body).await?;
+            let category = body.get("category").and_then(|v| v.as_str()).unwrap_or_default();
+            // the list only holds orderId and orderLinkId, symbol is known only when the scope has one
+            let symbol = body
+                .get("symbol")
+                .and_then(|v| v.as_str())
+                .and_then(|id| self.market_by_id(id, category).ok())
+                .map(|v| v.symbol.clone())
+                .unwrap_or_default();
+            let list = result.get("list").and_then(|v| v.as_array()).cloned().unwrap_or_default();
+            let orders = list
+                .into_iter()
+                .map(|mut order| {
+                    let get_str = |key: &str| order.get(key).and_then(|v| v.as_str()).filter(|v| !v.is_empty());
+                    let id = get_str("orderId").unwrap_or_default().to_string();
+                    let client_order_id = get_str("orderLinkId").map(|v| v.to_string());
+                    order["category"] = category.into();
+                    Order {
+                        id,
+                        client_order_id,
+                        status: Some("canceled".to_string()),
+                        symbol: symbol.clone(),
+                        info: order,
+                        ..Default::default()
+                    }
+                })
+                .collect::<Vec<_>>();
+            anyhow::Ok(orders)
+        });
+        let res = futures::future::try_join_all(futures).await?;
+        Ok(res.into_iter().flatten().collect())
+    }
+
+    // dead man's switch, orders of product are canceled when the connection is lost for timeout
+    // it requires disconnection protect (DCP) enabled for the account
+    // timeout: millisecond, 3s ~ 300s; product: SPOT, DERIVATIVES (default), OPTIONS
+    pub async fn cancel_all_orders_after(&self, timeout: i64, product: Option<&str>) -> Result<()> {
+        // https://bybit-exchange.github.io/docs/v5/order/dcp
+        let time_window = timeout / 1000;
+        anyhow::ensure!(
+            (3..=300).contains(&time_window),
+            "timeout should be between 3000 and 300000 ms"
+        );
+        let body = serde_json::json!({ "product": product.unwrap_or("DERIVATIVES"), "timeWindow": time_window });
+        self.private_post(self.api.cancel_all_orders_after, &body).await?;
+        Ok(())
+    }
+
+    pub async fn fetch_order(&self, id: &str, symbol: &str) -> Result<Order> {
+        // https://bybit-exchange.github.io/docs/v5/order/open-order
+        // https://bybit-exchange.github.io/docs/v5/order/order-list
+        self.ensure_markets().await?;
+        let market = self.market(symbol)?;
+        let query = [
+            ("category", category(&market)),
+            ("symbol", market.id.as_str()),
+            ("orderId", id),
+        ];
+        for path in [self.api.open_orders, self.api.order_history] {
+            let result = self.private_get(path, &query).await?;
+            if let Some(order) = result.pointer("/list/0") {
+                return Ok(self.parse_order(order, &market));
+            }
+        }
+        anyhow::bail!("order {id} not found")
+    }
+
+    // without symbol, open orders of every scope of order_scopes are fetched
+    pub async fn fetch_open_orders(
+        &self,
+        symbol: Option<&str>,
+        since: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<Order>> {
+        // https://bybit-exchange.github.io/docs/v5/order/open-order
+        let mut res = self.fetch_orders_by_scopes(self.api.open_orders, symbol, None, None).await?;
+        if let Some(since) = since {
+            res.retain(|v| v.timestamp.is_some_and(|ts| ts >= since));
+        }
+        filter_by_limit(&mut res, limit, since.is_some());
+        Ok(res)
+    }
+
+    pub async fn fetch_closed_orders(
+        &self,
+        symbol: Option<&str>,
+        since: Option<i64>,
+        until: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<Order>> {
+        let mut res = self.fetch_canceled_and_closed_orders(symbol, since, until, None).await?;
+        res.retain(|v| v.status.as_deref() == Some("closed"));
+        filter_by_limit(&mut res, limit, since.is_some());
+        Ok(res)
+    }
+
+    pub async fn fetch_canceled_orders(
+        &self,
+        symbol: Option<&str>,
+        since: Option<i64>,
+        until: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<Order>> {
+        let mut res = self.fetch_canceled_and_closed_orders(symbol, since, until, None).await?;
+        res.retain(|v| v.status.as_deref() == Some("canceled"));
+        filter_by_limit(&mut res, limit, since.is_some());
+        Ok(res)
+    }
+
+    // without since, bybit returns orders of the last 7 days before until
+    pub async fn fetch_canceled_and_closed_orders(
+        &self,
+        symbol: Option<&str>,
+        since: Option<i64>,
+        until: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<Order>> {
+        // https://bybit-exchange.github.io/docs/v5/order/order-list
+        let mut res = self.fetch_orders_by_scopes(self.api.order_history, symbol, since, until).await?;
+        res.retain(|v| matches!(v.status.as_deref(), Some("closed" | "canceled" | "rejected")));
+        filter_by_limit(&mut res, limit, since.is_some());
+        Ok(res)
+    }
+
+    pub async fn fetch_order_trades(
+        &self,
+        id: &str,
+        symbol: &str,
+        since: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<Trade>> {
+        // https://bybit-exchange.github.io/docs/v5/order/execution
+        self.ensure_markets().await?;
+        let market = self.market(symbol)?;
+        let category = category(&market);
+        let query = [
+            ("category", category.to_string()),
+            ("symbol", market.id.clone()),
+            ("orderId", id.to_string()),
+        ];
+        let list = self
+            .fetch_paged(
+                PagedApi::private(self.api.executions, 100).window(7 * DAY, "execTime"),
+                &query,
+                since,
+                None,
+                limit,
+            )
+            .await?;
+        let mut res = list.iter().filter_map(|v| self.parse_execution(v, category)).collect::<Vec<_>>();
+        res.sort_by_key(|v| v.timestamp);
+        Ok(res)
+    }
+
+    // real fills only, funding, settlement and delivery are left to fetch_my_executions
+    // limit is applied after the other executions are filtered out, so they don't take its place
+    pub async fn fetch_my_trades(
+        &self,
+        symbol: Option<&str>,
+        since: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<Trade>> {
+        let mut res = self.fetch_my_executions(symbol, since, None, None).await?;
+        res.retain(|v| v.info.get("execType").and_then(|v| v.as_str()).is_some_and(is_fill));
+        filter_by_limit(&mut res, limit, since.is_some());
+        Ok(res)
+    }
+
+    // exec_type: Trade, AdlTrade, Funding, BustTrade, Delivery, Settle, BlockTrade, MovePosition, FutureSpread
+    // all of them when None
+    pub async fn fetch_my_executions(
+        &self,
+        symbol: Option<&str>,
+        since: Option<i64>,
+        limit: Option<i64>,
+        exec_type: Option<&str>,
+    ) -> Result<Vec<Trade>> {
+        // https://bybit-exchange.github.io/docs/v5/order/execution
+        self.ensure_markets().await?;
+        let mut scopes = match symbol {
+            Some(symbol) => {
+                let market = self.market(symbol)?;
+                vec![vec![
+                    ("category", category(&market).to_string()),
+                    ("symbol", market.id.clone()),
+                ]]
+            }
+            None => ["spot", "linear", "inverse", "option"].map(|v| vec![("category", v.to_string())]).to_vec(),
+        };
+        if let Some(exec_type) = exec_type {
+            scopes.iter_mut().for_each(|v| v.push(("execType", exec_type.to_string())));
+        }
+
+        let futures = scopes.iter().map(|scope| async move {
+            let category = scope.first().map_or("", |(_, v)| v.as_str());
+            let list = self
+                .fetch_paged(
+                    PagedApi::private(self.api.executions, 100).window(7 * DAY, "execTime"),
+                    scope,
+                    since,
+                    None,
+                    limit,
+                )
+                .await?;
+            anyhow::Ok(list.iter().filter_map(|v| self.parse_execution(v, category)).collect::<Vec<_>>())
+        });
+        let mut res = futures::future::try_join_all(futures).await?.into_iter().flatten().collect::<Vec<_>>();
+        res.sort_by_key(|v| v.timestamp);
+        filter_by_limit(&mut res, limit, since.is_some());
+        Ok(res)
+    }
+
+    // the first position of symbol, there are two of them in hedge mode
+    pub async fn fetch_position(&self, symbol: &str) -> Result<Position> {
+        let positions = self.fetch_positions(&[symbol.to_string()]).await?;
+        positions.into_iter().next().with_context(|| format!("position {symbol} not found"))
+    }
+
+    // without symbols, positions of option and every settle coin of linear/inverse are fetched
+    pub async fn fetch_positions(&self, symbols: &[String]) -> Result<Vec<Position>> {
+        // https://bybit-exchange.github.io/docs/v5/position
+        let scopes = match symbols {
+            [symbol] => self.order_scopes(Some(symbol)).await?,
+            _ => self.order_scopes(None).await?,
+        };
+        let scopes = scopes.into_iter().filter(|v| v.first().is_some_and(|(_, category)| category != "spot"));
+        let futures = scopes.map(|scope| async move {
+            let category = scope.first().map_or("", |(_, v)| v.as_str());
+            let list = self.fetch_paged(PagedApi::private(self.api.positions, 200), &scope, None, None, None).await?;
+            anyhow::Ok(list.iter().filter_map(|v| self.parse_position(v, category)).collect::<Vec<_>>())
+        });
+        let mut res = futures::future::try_join_all(futures).await?.into_iter().flatten().collect::<Vec<_>>();
+        if !symbols.is_empty() {
+            res.retain(|v| symbols.contains(&v.symbol));
+        }
+        Ok(res)
+    }
+
+    // closed positions of linear and inverse
+    pub async fn fetch_positions_history(
+        &self,
+        symbol: Option<&str>,
+        since: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<Position>> {
+        // https://bybit-exchange.github.io/docs/v5/position/close-pnl
+        self.ensure_markets().await?;
+        let scopes = match symbol {
+            Some(symbol) => {
+                let market = self.market(symbol)?;
+                vec![vec![
+                    ("category", category(&market).to_string()),
+                    ("symbol", market.id.clone()),
+                ]]
+            }
+            None => ["linear", "inverse"].map(|v| vec![("category", v.to_string())]).to_vec(),
+        };
+        let futures = scopes.iter().map(|scope| async move {
+            let category = scope.first().map_or("", |(_, v)| v.as_str());
+            let list = self
+                .fetch_paged(
+                    PagedApi::private(self.api.closed_pnl, 100).window(7 * DAY, "createdTime"),
+                    scope,
+                    since,
+                    None,
+                    limit,
+                )
+                .await?;
+            anyhow::Ok(list.iter().filter_map(|v| self.parse_closed_position(v, category)).collect::<Vec<_>>())
+        });
+        let mut res = futures::future::try_join_all(futures).await?.into_iter().flatten().collect::<Vec<_>>();
+        res.sort_by_key(|v| v.timestamp);
+        filter_by_limit(&mut res, limit, since.is_some());
+        Ok(res)
+    }
+
+    pub async fn fetch_leverage(&self, symbol: &str) -> Result<Leverage> {
+        let positions = self.fetch_positions(&[symbol.to_string()]).await?;
+        let first = positions.first().with_context(|| format!("position {symbol} not found"))?;
+        let mut leverage = Leverage {
+            symbol: first.symbol.clone(),
+            margin_mode: first.margin_mode.clone(),
+            long_leverage: None,
+            short_leverage: None,
+            info: Value::Array(positions.iter().map(|v| v.info.clone()).collect()),
+        };
+        // one-way mode has a single position for both sides
+        for position in &positions {
+            match position.position_idx {
+                Some(1) => leverage.long_leverage = position.leverage,
+                Some(2) => leverage.short_leverage = position.leverage,
+                _ => {
+                    leverage.long_leverage = position.leverage;
+                    leverage.short_leverage = position.leverage;
+                }
+            }
+        }
+        Ok(leverage)
+    }
+
+    pub async fn set_leverage(&self, leverage: f64, symbol: &str) -> Result<()> {
+        // https://bybit-exchange.github.io/docs/v5/position/leverage
+        const LEVERAGE_NOT_MODIFIED: i64 = 110043;
+        self.ensure_markets().await?;
+        let market = self.market(symbol)?;
+        anyhow::ensure!(
+            market.linear.is_some() || market.inverse.is_some(),
+            "{symbol} is not linear or inverse"
+        );
+        let leverage = leverage.to_string();
+        let body = serde_json::json!({
+            "category": category(&market),
+            "symbol": market.id,
+            "buyLeverage": leverage,
+            "sellLeverage": leverage,
+        });
+        self.private_post_not_modified(self.api.set_leverage, &body, &[LEVERAGE_NOT_MODIFIED]).await?;
+        Ok(())
+    }
+
+    // margin_mode: cross, isolated, portfolio
+    // unified account switches the whole account and ignores symbol, portfolio is only available to it
+    // classic account switches symbol and keeps its current leverage
+    pub async fn set_margin_mode(&self, margin_mode: &str, symbol: Option<&str>) -> Result<()> {
+        const MARGIN_MODE_NOT_MODIFIED: i64 = 110026;
+        if self.is_unified_enabled().await? {
+            // https://bybit-exchange.github.io/docs/v5/account/set-margin-mode
+            let set_margin_mode = match margin_mode {
+                "isolated" => "ISOLATED_MARGIN",
+                "cross" => "REGULAR_MARGIN",
+                "portfolio" => "PORTFOLIO_MARGIN",
+                margin_mode => anyhow::bail!("invalid margin mode {margin_mode}"),
+            };
+            let body = serde_json::json!({ "setMarginMode": set_margin_mode });
+            self.private_post(self.api.set_margin_mode, &body).await?;
+            return Ok(());
+        }
+
+        // https://bybit-exchange.github.io/docs/v5/position/cross-isolate
+        let symbol = symbol.context("symbol is required by classic account")?;
+        let trade_mode = match margin_mode {
+            "cross" => 0,
+            "isolated" => 1,
+            margin_mode => anyhow::bail!("invalid margin mode {margin_mode} for classic account"),
+        };
+        let leverage = self.fetch_leverage(symbol).await?;
+        let market = self.market(symbol)?;
+        let body = serde_json::json!({
+            "category": category(&market),
+            "symbol": market.id,
+            "tradeMode": trade_mode,
+            "buyLeverage": leverage.long_leverage.context("no leverage")?.to_string(),
+            "sellLeverage": leverage.short_leverage.context("no leverage")?.to_string(),
+        });
+        self.private_post_not_modified(self.api.switch_isolated, &body, &[MARGIN_MODE_NOT_MODIFIED]).await?;
+        Ok(())
+    }
+
+    // hedged: true for hedge mode (both sides), false for one-way mode
+    // without symbol, all linear symbols settled in USDT are switched
+    pub async fn set_position_mode(&self, hedged: bool, symbol: Option<&str>) -> Result<()> {
+        // https://bybit-exchange.github.io/docs/v5/position/position-mode
+        const POSITION_MODE_NOT_MODIFIED: i64 = 110025;
+        let mode = if hedged { 3 } else { 0 };
+        let body = match symbol {
+            Some(symbol) => {
+                self.ensure_markets().await?;
+                let market = self.market(symbol)?;
+                serde_json::json!({ "category": category(&market), "symbol": market.id, "mode": mode })
+            }
+            None => serde_json::json!({ "category": "linear", "coin": "USDT", "mode": mode }),
+        };
+        self.private_post_not_modified(self.api.set_position_mode, &body, &[POSITION_MODE_NOT_MODIFIED]).await?;
+        Ok(())
+    }
+
+    pub async fn is_unified_enabled(&self) -> Result<bool> {
+        // https://bybit-exchange.github.io/docs/v5/account/account-info
+        // unifiedMarginStatus: 1 classic account, the others are unified account
+        let result = self.private_get(self.api.account_info, &[]).await?;
+        let status = result.get("unifiedMarginStatus").and_then(|v| v.a_o_p_i64()).context("no unifiedMarginStatus")?;
+        Ok(status != 1)
+    }
+
+    fn parse_position(&self, position: &Value, category: &str) -> Option<Position> {
+        let market = self.market_by_id(position.get("symbol").and_then(|v| v.as_str())?, category).ok()?;
+        // bybit returns 0 or empty string for unset prices
+        let get_f64 = |key: &str| position.get(key).and_then(|v| v.a_o_p_f64()).filter(|v| *v != 0.0);
+        let timestamp = position.get("createdTime").and_then(|v| v.a_o_p_i64());
+        let position_idx = position.get("positionIdx").and_then(|v| v.a_o_p_i64());
+        let side = match position.get("side").and_then(|v| v.as_str()) {
+            Some("Buy") => Some("long".to_string()),
+            Some("Sell") => Some("short".to_string()),
+            _ => None,
+        };
+        let margin_mode = position
+            .get("tradeMode")
+            .and_then(|v| v.a_o_p_i64())
+            .map(|v| if v == 1 { "isolated" } else { "cross" }.to_string());
+        Some(Position {
+            id: None,
+            symbol: market.symbol.clone(),
+            timestamp,
+            datetime: timestamp.and_then(iso_8601),
+            last_update_timestamp: position.get("updatedTime").and_then(|v| v.a_o_p_i64()),
+            side,
+            contracts: position.get("size").and_then(|v| v.a_o_p_f64()),
+            contract_size: market.contract_size,
+            notional: get_f64("positionValue"),
+            leverage: get_f64("leverage"),
+            entry_price: get_f64("avgPrice"),
+            mark_price: get_f64("markPrice"),
+            last_price: None,
+            liquidation_price: get_f64("liqPrice"),
+            unrealized_pnl: position.get("unrealisedPnl").and_then(|v| v.a_o_p_f64()),
+            realized_pnl: position.get("cumRealisedPnl").and_then(|v| v.a_o_p_f64()),
+            collateral: get_f64("positionBalance"),
+            initial_margin: get_f64("positionIM"),
+            maintenance_margin: get_f64("positionMM"),
+            margin_mode,
+            hedged: position_idx.map(|v| v != 0),
+            position_idx,
+            stop_loss_price: get_f64("stopLoss"),
+            take_profit_price: get_f64("takeProfit"),
+            info: position.clone(),
+        })
+    }
+
+    fn parse_closed_position(&self, position: &Value, category: &str) -> Option<Position> {
+        let market = self.market_by_id(position.get("symbol").and_then(|v| v.as_str())?, category).ok()?;
+        let timestamp = position.get("createdTime").and_then(|v| v.a_o_p_i64());
+        // side of closed pnl is the side of the closing order
+        let side = match position.get("side").and_then(|v| v.as_str()) {
+            Some("Sell") => Some("long".to_string()),
+            Some("Buy") => Some("short".to_string()),
+            _ => None,
+        };
+        Some(Position {
+            id: position.get("orderId").and_then(|v| v.as_str()).map(|v| v.to_string()),
+            symbol: market.symbol.clone(),
+            timestamp,
+            datetime: timestamp.and_then(iso_8601),
+            last_update_timestamp: position.get("updatedTime").and_then(|v| v.a_o_p_i64()),
+            side,
+            contracts: position.get("closedSize").and_then(|v| v.a_o_p_f64()),
+            contract_size: market.contract_size,
+            notional: position.get("cumEntryValue").and_then(|v| v.a_o_p_f64()),
+            leverage: position.get("leverage").and_then(|v| v.a_o_p_f64()),
+            entry_price: position.get("avgEntryPrice").and_then(|v| v.a_o_p_f64()),
+            last_price: position.get("avgExitPrice").and_then(|v| v.a_o_p_f64()),
+            realized_pnl: position.get("closedPnl").and_then(|v| v.a_o_p_f64()),
+            info: position.clone(),
+            ..Default::default()
+        })
+    }
+
+    // orders of all scopes sorted by timestamp
+    async fn fetch_orders_by_scopes(
+        &self,
+        path: &str,
+        symbol: Option<&str>,
+        since: Option<i64>,
+        until: Option<i64>,
+    ) -> Result<Vec<Order>> {
+        let scopes = self.order_scopes(symbol).await?;
+        let futures = scopes.iter().map(|scope| async move {
+            let category = scope.first().map_or("", |(_, v)| v.as_str());
+            let list = self
+                .fetch_paged(
+                    PagedApi::private(path, 50).window(7 * DAY, "createdTime"),
+                    scope,
+                    since,
+                    until,
+                    None,
+                )
+                .await?;
+            let orders = list
+                .iter()
+                .filter_map(|order| {
+                    let id = order.get("symbol").and_then(|v| v.as_str())?;
+                    let market = self.market_by_id(id, category).ok()?;
+                    Some(self.parse_order(order, &market))
+                })
+                .collect::<Vec<_>>();
+            anyhow::Ok(orders)
+        });
+        let mut res = futures::future::try_join_all(futures).await?.into_iter().flatten().collect::<Vec<_>>();
+        res.sort_by_key(|v| v.timestamp);
+        Ok(res)
+    }
+
+    // linear and inverse order endpoints need one of symbol, baseCoin and settleCoin,
+    // without symbol the scopes are spot, option and every settle coin of linear/inverse
+    // the first item of each scope is category
+    async fn order_scopes(&self, symbol: Option<&str>) -> Result<Vec<Vec<(&'static str, String)>>> {
+        self.ensure_markets().await?;
+        if let Some(symbol) = symbol {
+            let market = self.market(symbol)?;
+            return Ok(vec![vec![
+                ("category", category(&market).to_string()),
+                ("symbol", market.id.clone()),
+            ]]);
+        }
+
+        let mut scopes = vec![
+            vec![("category", "spot".to_string())],
+            vec![("category", "option".to_string())],
+        ];
+        let markets = self.markets.read().map_err(|e| anyhow::anyhow!("{e}"))?;
+        let mut settles = markets
+            .by_symbol
+            .values()
+            .filter(|v| v.contract && !v.option)
+            .filter_map(|v| Some((category(v), v.settle_id.clone()?)))
+            .collect::<Vec<_>>();
+        settles.sort();
+        settles.dedup();
+        scopes.extend(
+            settles
+                .into_iter()
+                .map(|(category, settle)| vec![("category", category.to_string()), ("settleCoin", settle)]),
+        );
+        Ok(scopes)
+    }
+
+    // GET of list endpoints, follows nextPageCursor until limit is reached
+    // with since, [since, until] is split into windows of the max range of the endpoint,
+    // rows are then sorted by time_key so that limit keeps the earliest ones
+    async fn fetch_paged(
+        &self,
+        api: PagedApi<'_>,
+        query: &[(&str, String)],
+        since: Option<i64>,
+        until: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<Value>> {
+        let PagedApi {
+            path,
+            private,
+            page_limit,
+            window,
+            time_key,
+        } = api;
+        anyhow::ensure!(since.is_none() || window.is_some(), "{path} has no time range");
+        let windows = match since.zip(window) {
+            Some((since, window)) => {
+                let until = until.unwrap_or_else(timestamp);
+                let mut windows = Vec::new();
+                let mut start = since;
+                while start <= until {
+                    let end = (start + window - 1).min(until);
+                    windows.push((Some(start), Some(end)));
+                    start = end + 1;
+                }
+                windows
+            }
+            None => vec![(None, until)],
+        };
+
+        let mut res = Vec::new();
+        for (start, end) in windows {
+            let mut rows = Vec::new();
+            let mut cursor = String::new();
+            loop {
+                let mut page_query = query.to_vec();
+                page_query.push(("limit", page_limit.to_string()));
+                if let Some(start) = start {
+                    page_query.push(("startTime", start.to_string()));
+                }
+                if let Some(end) = end {
+                    page_query.push(("endTime", end.to_string()));
+                }
+                if !cursor.is_empty() {
+                    page_query.push(("cursor", cursor.clone()));
+                }
+                let page_query = page_query.iter().map(|(k, v)| (*k, v.as_str())).collect::<Vec<_>>();
+                let mut result = if private {
+                    self.private_get(path, &page_query).await?
+                } else {
+                    self.public_get(path, &page_query).await?
+                };
+                cursor = result.get("nextPageCursor").and_then(|v| v.as_str()).unwrap_or_default().to_string();
+                if let Some(list) = result.get_mut("list").and_then(|v| v.as_array_mut()) {
+                    rows.append(list);
+                }
+                // pages are newest first, so a window from since is read to its end before being cut
+                if start.is_none() && limit.is_some_and(|limit| rows.len() as i64 >= limit) {
+                    break;
+                }
+                if cursor.is_empty() {
+                    break;
+                }
+            }
+            if start.is_some() {
+                rows.sort_by_key(|v| v.get(time_key).and_then(|v| v.a_o_p_i64()));
+            }
+            res.append(&mut rows);
+            if limit.is_some_and(|limit| res.len() as i64 >= limit) {
+                break;
+            }
+        }
+        if let Some(limit) = limit {
+            res.truncate(limit.max(0) as usize);
+        }
+        Ok(res)
+    }
+
+    fn parse_execution(&self, trade: &Value, category: &str) -> Option<Trade> {
+        let id = trade.get("execId").and_then(|v| v.as_str())?;
+        let market = self.market_by_id(trade.get("symbol").and_then(|v| v.as_str())?, category).ok()?;
+        let get_str = |key: &str| trade.get(key).and_then(|v| v.as_str()).filter(|v| !v.is_empty());
+        let timestamp = trade.get("execTime").and_then(|v| v.a_o_p_i64());
+        let fee = trade.get("execFee").and_then(|v| v.a_o_p_f64()).map(|cost| {
+            // spot fee currency is returned as feeCurrency, contract fee is in settle currency
+            let currency = get_str("feeCurrency").map(|v| self.get_currency_code(v)).or(market.settle.clone());
+            Fee {
+                cost: Some(cost),
+                currency,
+                rate: trade.get("feeRate").and_then(|v| v.a_o_p_f64()),
+            }
+        });
+        Some(Trade {
+            id: id.to_string(),
+            order: get_str("orderId").map(|v| v.to_string()),
+            timestamp,
+            datetime: timestamp.and_then(iso_8601),
+            symbol: market.symbol.clone(),
+            r#type: get_str("orderType").map(|v| v.to_lowercase()),
+            side: get_str("side").map(|v| v.to_lowercase()),
+            taker_or_maker: trade
+                .get("isMaker")
+                .and_then(|v| v.as_bool())
+                .map(|v| if v { "maker" } else { "taker" }.to_string()),
+            price: trade.get("execPrice").and_then(|v| v.a_o_p_f64()),
+            amount: trade.get("execQty").and_then(|v| v.a_o_p_f64()),
+            cost: trade.get("execValue").and_then(|v| v.a_o_p_f64()),
+            fee,
+            info: trade.clone(),
+        })
+    }
+
+    fn same_category(&self, markets: &[Arc<Market>]) -> Result<&'static str> {
+        let category = markets.first().map(|v| category(v)).context("no order")?;
+        anyhow::ensure!(
+            markets.iter().all(|v| super::category(v) == category),
+            "orders are not in the same category"
+        );
+        Ok(category)
+    }
+
+    fn amend_request_body(&self, id: &str, request: &OrderRequest, market: &Market) -> serde_json::Map<String, Value> {
+        let mut body = serde_json::Map::new();
+        body.insert("symbol".to_string(), market.id.clone().into());
+        body.insert("orderId".to_string(), id.into());
+        if request.amount > 0.0 {
+            body.insert(
+                "qty".to_string(),
+                to_precision(request.amount, market.precision.amount, true).into(),
+            );
+        }
+        if let Some(price) = request.price {
+            body.insert(
+                "price".to_string(),
+                to_precision(price, market.precision.price, false).into(),
+            );
+        }
+        if let Some(trigger_price) = request.trigger_price {
+            body.insert(
+                "triggerPrice".to_string(),
+                to_precision(trigger_price, market.precision.price, false).into(),
+            );
+        }
+        if let Some(take_profit) = request.take_profit_price {
+            body.insert(
+                "takeProfit".to_string(),
+                to_precision(take_profit, market.precision.price, false).into(),
+            );
+        }
+        if let Some(stop_loss) = request.stop_loss_price {
+            body.insert(
+                "stopLoss".to_string(),
+                to_precision(stop_loss, market.precision.price, false).into(),
+            );
+        }
+        body
+    }
+
+    // bodies of orders in category are sent in batches of batch_limit, orders are returned in the order of bodies
+    // a failed batch doesn't discard the others, its orders are rejected with the error in info
+    async fn post_batch_orders(
+        &self,
+        path: &str,
+        category: &str,
+        bodies: Vec<Value>,
+        markets: &[Arc<Market>],
+    ) -> Vec<Order> {
+        let limit = batch_limit(category);
+        let futures = bodies.chunks(limit).zip(markets.chunks(limit)).map(|(bodies, markets)| async move {
+            let body = serde_json::json!({ "category": category, "request": bodies });
+            let resp = self.private_post_raw(path, &body).await;
+            resp.and_then(|resp| self.parse_batch_orders(&resp, markets)).unwrap_or_else(|e| {
+                let info = serde_json::json!({ "msg": format!("{e:#}") });
+                markets
+                    .iter()
+                    .map(|market| Order {
+                        symbol: market.symbol.clone(),
+                        status: Some("rejected".to_string()),
+                        info: info.clone(),
+                        ..Default::default()
+                    })
+                    .collect()
+            })
+        });
+        futures::future::join_all(futures).await.into_iter().flatten().collect()
+    }
+
+    // batch endpoints return orders in result.list and the status of each one in retExtInfo.list
+    fn parse_batch_orders(&self, resp: &Value, markets: &[Arc<Market>]) -> Result<Vec<Order>> {
+        let list = resp.pointer("/result/list").and_then(|v| v.as_array()).context("no list")?;
+        let ext_list = resp.pointer("/retExtInfo/list").and_then(|v| v.as_array());
+        let res = markets
+            .iter()
+            .enumerate()
+            .map(|(i, market)| {
+                let ext = ext_list.and_then(|v| v.get(i));
+                let code = ext.and_then(|v| v.get("code")).and_then(|v| v.a_o_p_i64()).unwrap_or_default();
+                match list.get(i) {
+                    Some(item) if code == 0 => self.parse_order(item, market),
+                    _ => Order {
+                        symbol: market.symbol.clone(),
+                        status: Some("rejected".to_string()),
+                        info: ext.cloned().unwrap_or_default(),
+                        ..Default::default()
+                    },
+                }
+            })
+            .collect();
+        Ok(res)
+    }
+
+    // account_type: ccxt name like unified, spot, contract, funding or bybit name like UNIFIED, default unified
+    pub async fn fetch_balance(&self, account_type: Option<&str>) -> Result<Balances> {
+        let account_type = self.account_type(account_type.unwrap_or("unified"))?;
+        let mut balances = HashMap::new();
+        let info = if account_type == "FUND" {
+            // https://bybit-exchange.github.io/docs/v5/asset/balance/all-balance
+            let result = self.private_get(self.api.fund_balance, &[("accountType", account_type)]).await?;
+            let coins = result.get("balance").and_then(|v| v.as_array()).context("no balance")?;
+            for coin in coins {
+                let Some(code) = coin.get("coin").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                let total = coin.get("walletBalance").and_then(|v| v.a_o_p_f64());
+                let free = coin.get("transferBalance").and_then(|v| v.a_o_p_f64());
+                let balance = Balance {
+                    free,
+                    used: total.zip(free).map(|(total, free)| total - free),
+                    total,
+                    debt: None,
+                };
+                balances.insert(self.get_currency_code(code), balance);
+            }
+            result
+        } else {
+            // https://bybit-exchange.github.io/docs/v5/account/wallet-balance
+            let result = self.private_get(self.api.wallet_balance, &[("accountType", account_type)]).await?;
+            let account = result.pointer("/list/0").context("no account")?;
+            let coins = account.get("coin").and_then(|v| v.as_array()).context("no coin")?;
+            // empty string when not applicable
+            let get_f64 = |coin: &Value, key: &str| coin.get(key).and_then(|v| v.a_o_p_f64());
+            for coin in coins {
+                let Some(code) = coin.get("coin").and_then(|v| v.as_str()) else {
+                    continue;
+                };
+                let total = get_f64(coin, "walletBalance");
+                let used = ["locked", "totalOrderIM", "totalPositionIM"]
+                    .iter()
+                    .filter_map(|key| get_f64(coin, key))
+                    .reduce(|a, b| a + b);
+                let free = get_f64(coin, "availableToWithdraw").or(total.map(|total| total - used.unwrap_or_default()));
+                let balance = Balance {
+                    free,
+                    used,
+                    total,
+                    debt: get_f64(coin, "borrowAmount"),
+                };
+                balances.insert(self.get_currency_code(code), balance);
+            }
+            result
+        };
+
+        let timestamp = timestamp();
+        Ok(Balances {
+            timestamp: Some(timestamp),
+            datetime: iso_8601(timestamp),
+            balances,
+            info,
+        })
+    }
+
+    // network: unified network code like ERC20, may be None only when the currency has a single chain
+    // as a deposit to an address of another chain can't be recovered
+    pub async fn fetch_deposit_address(&self, code: &str, network: Option<&str>) -> Result<DepositAddress> {
+        let mut addresses = self.fetch_deposit_address_list(code, network).await?;
+        match network {
+            Some(network) => {
+                let index = addresses.iter().position(|v| v.network == network);
+                index.map(|i| addresses.swap_remove(i)).with_context(|| format!("no {code} address of {network}"))
+            }
+            None => {
+                let networks = addresses.iter().map(|v| v.network.as_str()).collect::<Vec<_>>();
+                anyhow::ensure!(
+                    networks.len() <= 1,
+                    "{code} has networks {}, specify one",
+                    networks.join(", ")
+                );
+                addresses.pop().with_context(|| format!("no {code} address"))
+            }
+        }
+    }
+
+    // unified network code -> address, only network is fetched when given
+    pub async fn fetch_deposit_addresses_by_network(
+        &self,
+        code: &str,
+        network: Option<&str>,
+    ) -> Result<HashMap<String, DepositAddress>> {
+        let addresses = self.fetch_deposit_address_list(code, network).await?;
+        Ok(addresses.into_iter().map(|v| (v.network.clone(), v)).collect())
+    }
+
+    // addresses in the order of chains of bybit
+    async fn fetch_deposit_address_list(&self, code: &str, network: Option<&str>) -> Result<Vec<DepositAddress>> {
+        // https://bybit-exchange.github.io/docs/v5/asset/deposit/master-deposit-addr
+        let mut query = vec![("coin", code)];
+        query.extend(network.map(|v| ("chainType", self.network_id(v))));
+        let result = self.private_get(self.api.deposit_address, &query).await?;
+        let chains = result.get("chains").and_then(|v| v.as_array()).context("no chains")?;
+        let mut res = Vec::new();
+        for chain in chains {
+            let Some(network_id) = chain.get("chain").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let Some(address) = chain.get("addressDeposit").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            res.push(DepositAddress {
+                currency: self.get_currency_code(code),
+                network: self.network_code(network_id).to_string(),
+                address: address.to_string(),
+                tag: chain.get("tagDeposit").and_then(|v| v.as_str()).filter(|v| !v.is_empty()).map(|v| v.to_string()),
+                info: chain.clone(),
+            });
+        }
+        Ok(res)
+    }
+
+    // without since, bybit returns records of the last 30 days
+    pub async fn fetch_deposits(
+        &self,
+        code: Option<&str>,
+        since: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<Transaction>> {
+        // https://bybit-exchange.github.io/docs/v5/asset/deposit/deposit-record
+        let query = code.map(|v| ("coin", v.to_string())).into_iter().collect::<Vec<_>>();
+        let list = self
+            .fetch_paged(
+                PagedApi::private(self.api.deposits, 50).window(30 * DAY, "successAt"),
+                &query,
+                since,
+                None,
+                limit,
+            )
+            .await?;
+        let mut res = list.iter().filter_map(|v| self.parse_transaction(v, "deposit")).collect::<Vec<_>>();
+        res.sort_by_key(|v| v.timestamp);
+        Ok(res)
+    }
+
+    // without since, bybit returns records of the last 30 days
+    pub async fn fetch_withdrawals(
+        &self,
+        code: Option<&str>,
+        since: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<Transaction>> {
+        // https://bybit-exchange.github.io/docs/v5/asset/withdraw/withdraw-record
+        let query = code.map(|v| ("coin", v.to_string())).into_iter().collect::<Vec<_>>();
+        let list = self
+            .fetch_paged(
+                PagedApi::private(self.api.withdrawals, 50).window(30 * DAY, "createTime"),
+                &query,
+                since,
+                None,
+                limit,
+            )
+            .await?;
+        let mut res = list.iter().filter_map(|v| self.parse_transaction(v, "withdrawal")).collect::<Vec<_>>();
+        res.sort_by_key(|v| v.timestamp);
+        Ok(res)
+    }
+
+    // network: unified network code like ERC20
+    pub async fn withdraw(
+        &self,
+        code: &str,
+        amount: f64,
+        address: &str,
+        tag: Option<&str>,
+        network: Option<&str>,
+    ) -> Result<Transaction> {
+        // https://bybit-exchange.github.io/docs/v5/asset/withdraw
+        let mut body = serde_json::json!({
+            "coin": code,
+            "amount": self.currency_to_precision(code, amount, network).await?,
+            "address": address,
+            "timestamp": timestamp(),
+        });
+        if let Some(tag) = tag {
+            body["tag"] = tag.into();
+        }
+        if let Some(network) = network {
+            body["chain"] = self.network_id(network).into();
+        }
+        let result = self.private_post(self.api.withdraw, &body).await?;
+        let timestamp = timestamp();
+        Ok(Transaction {
+            id: result.get("id").and_then(|v| v.as_str()).unwrap_or_default().to_string(),
+            timestamp: Some(timestamp),
+            datetime: iso_8601(timestamp),
+            address: Some(address.to_string()),
+            tag: tag.map(|v| v.to_string()),
+            r#type: "withdrawal".to_string(),
+            amount: Some(amount),
+            currency: self.get_currency_code(code),
+            status: Some("pending".to_string()),
+            network: network.map(|v| v.to_string()),
+            info: result,
+            ..Default::default()
+        })
+    }
+
+    // from_account, to_account: ccxt account name like funding, unified or bybit account name like FUND
+    pub async fn transfer(
+        &self,
+        code: &str,
+        amount: f64,
+        from_account: &str,
+        to_account: &str,
+    ) -> Result<TransferEntry> {
+        // https://bybit-exchange.github.io/docs/v5/asset/transfer/create-inter-transfer
+        let transfer_id = uuid::Uuid::new_v4().to_string();
+        let from_account_type = self.account_type(from_account)?;
+        let to_account_type = self.account_type(to_account)?;
+        let body = serde_json::json!({
+            "transferId": transfer_id,
+            "coin": code,
+            "amount": self.currency_to_precision(code, amount, None).await?,
+            "fromAccountType": from_account_type,
+            "toAccountType": to_account_type,
+        });
+        let result = self.private_post(self.api.transfer, &body).await?;
+        let timestamp = timestamp();
+        Ok(TransferEntry {
+            id: result.get("transferId").and_then(|v| v.as_str()).unwrap_or(&transfer_id).to_string(),
+            timestamp: Some(timestamp),
+            datetime: iso_8601(timestamp),
+            currency: self.get_currency_code(code),
+            amount: Some(amount),
+            from_account: Some(self.account_name(from_account_type).to_string()),
+            to_account: Some(self.account_name(to_account_type).to_string()),
+            status: result.get("status").and_then(|v| v.as_str()).map(|v| transfer_status(v).to_string()),
+            info: result,
+        })
+    }
+
+    // without since, bybit returns transfers of the last 7 days
+    pub async fn fetch_transfers(
+        &self,
+        code: Option<&str>,
+        since: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<TransferEntry>> {
+        // https://bybit-exchange.github.io/docs/v5/asset/transfer/inter-transfer-list
+        let query = code.map(|v| ("coin", v.to_string())).into_iter().collect::<Vec<_>>();
+        let list = self
+            .fetch_paged(
+                PagedApi::private(self.api.transfers, 50).window(7 * DAY, "timestamp"),
+                &query,
+                since,
+                None,
+                limit,
+            )
+            .await?;
+        let mut res = list.iter().filter_map(|v| self.parse_transfer(v)).collect::<Vec<_>>();
+        res.sort_by_key(|v| v.timestamp);
+        Ok(res)
+    }
+
+    fn parse_transfer(&self, transfer: &Value) -> Option<TransferEntry> {
+        let get_str = |key: &str| transfer.get(key).and_then(|v| v.as_str()).filter(|v| !v.is_empty());
+        let timestamp = transfer.get("timestamp").and_then(|v| v.a_o_p_i64());
+        Some(TransferEntry {
+            id: get_str("transferId")?.to_string(),
+            timestamp,
+            datetime: timestamp.and_then(iso_8601),
+            currency: self.get_currency_code(get_str("coin")?),
+            amount: transfer.get("amount").and_then(|v| v.a_o_p_f64()),
+            from_account: get_str("fromAccountType").map(|v| self.account_name(v).to_string()),
+            to_account: get_str("toAccountType").map(|v| self.account_name(v).to_string()),
+            status: get_str("status").map(|v| transfer_status(v).to_string()),
+            info: transfer.clone(),
+        })
+    }
+
+    // entries of the contract transaction log, which holds inverse contracts of unified account too,
+    // merged with the transaction log of UNIFIED on unified account, each tagged with its account
+    // without since, bybit returns entries of the last 24 hours
+    pub async fn fetch_ledger(
+        &self,
+        code: Option<&str>,
+        since: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<LedgerEntry>> {
+        self.ensure_markets().await?;
+        let query = code.map(|v| ("currency", v.to_string())).into_iter().collect::<Vec<_>>();
+        let mut unified_query = query.clone();
+        unified_query.push(("accountType", "UNIFIED".to_string()));
+        let unified = async {
+            if !self.is_unified_enabled().await? {
+                return anyhow::Ok(Vec::new());
+            }
+            // https://bybit-exchange.github.io/docs/v5/account/transaction-log
+            let api = PagedApi::private(self.api.transaction_log, 50).window(7 * DAY, "transactionTime");
+            self.fetch_paged(api, &unified_query, since, None, limit).await
+        };
+        // https://bybit-exchange.github.io/docs/v5/account/contract-transaction-log
+        let contract_api = PagedApi::private(self.api.contract_transaction_log, 50).window(7 * DAY, "transactionTime");
+        let contract = self.fetch_paged(contract_api, &query, since, None, limit);
+        let (unified, contract) = tokio::try_join!(unified, contract)?;
+
+        let unified = unified.iter().filter_map(|v| self.parse_ledger_entry(v, "unified"));
+        let contract = contract.iter().filter_map(|v| self.parse_ledger_entry(v, "contract"));
+        let mut res = unified.chain(contract).collect::<Vec<_>>();
+        res.sort_by_key(|v| v.timestamp);
+        filter_by_limit(&mut res, limit, since.is_some());
+        Ok(res)
+    }
+
+    fn parse_ledger_entry(&self, entry: &Value, account: &str) -> Option<LedgerEntry> {
+        let get_str = |key: &str| entry.get(key).and_then(|v| v.as_str()).filter(|v| !v.is_empty());
+        let code = self.get_currency_code(get_str("currency")?);
+        let timestamp = entry.get("transactionTime").and_then(|v| v.a_o_p_i64());
+        // change is signed, cashBalance is the balance after the change
+        let change = entry.get("change").and_then(|v| v.a_o_p_f64());
+        let after = entry.get("cashBalance").and_then(|v| v.a_o_p_f64());
+        let fee = entry.get("fee").and_then(|v| v.a_o_p_f64()).map(|cost| Fee {
+            cost: Some(cost),
+            currency: Some(code.clone()),
+            rate: entry.get("feeRate").and_then(|v| v.a_o_p_f64()),
+        });
+        Some(LedgerEntry {
+            id: get_str("id")?.to_string(),
+            timestamp,
+            datetime: timestamp.and_then(iso_8601),
+            direction: change.map(|v| if v < 0.0 { "out" } else { "in" }.to_string()),
+            account: Some(account.to_string()),
+            reference_id: get_str("tradeId").or(get_str("orderId")).map(|v| v.to_string()),
+            r#type: get_str("type").map(|v| ledger_entry_type(v).to_string()),
+            currency: code,
+            symbol: get_str("symbol")
+                .zip(get_str("category"))
+                .and_then(|(id, category)| self.market_by_id(id, category).ok())
+                .map(|v| v.symbol.clone()),
+            amount: change.map(f64::abs),
+            before: after.zip(change).map(|(after, change)| after - change),
+            after,
+            status: Some("ok".to_string()),
+            fee,
+            info: entry.clone(),
+        })
+    }
+
+    // r#type: deposit, withdrawal
+    fn parse_transaction(&self, transaction: &Value, r#type: &str) -> Option<Transaction> {
+        let get_str = |key: &str| transaction.get(key).and_then(|v| v.as_str()).filter(|v| !v.is_empty());
+        let code = self.get_currency_code(get_str("coin")?);
+        let (id, timestamp, updated, fee) = if r#type == "deposit" {
+            let timestamp = transaction.get("successAt").and_then(|v| v.a_o_p_i64());
+            (get_str("id"), timestamp, timestamp, transaction.get("depositFee"))
+        } else {
+            (
+                get_str("withdrawId"),
+                transaction.get("createTime").and_then(|v| v.a_o_p_i64()),
+                transaction.get("updateTime").and_then(|v| v.a_o_p_i64()),
+                transaction.get("withdrawFee"),
+            )
+        };
+        let fee = fee.and_then(|v| v.a_o_p_f64()).map(|cost| Fee {
+            cost: Some(cost),
+            currency: Some(code.clone()),
+            rate: None,
+        });
+        Some(Transaction {
+            id: id.or(get_str("txID"))?.to_string(),
+            txid: get_str("txID").map(|v| v.to_string()),
+            timestamp,
+            datetime: timestamp.and_then(iso_8601),
+            address: get_str("toAddress").map(|v| v.to_string()),
+            tag: get_str("tag").map(|v| v.to_string()),
+            r#type: r#type.to_string(),
+            amount: transaction.get("amount").and_then(|v| v.a_o_p_f64()),
+            currency: code,
+            status: transaction
+                .get("status")
+                .and_then(|v| v.as_str().map(|v| v.to_string()).or(v.as_i64().map(|v| v.to_string())))
+                .map(|v| transaction_status(&v).to_string()),
+            updated,
+            network: get_str("chain").map(|v| self.network_code(v).to_string()),
+            fee,
+            info: transaction.clone(),
+        })
+    }
+
+    // hourly borrow rate of unified account
+    pub async fn fetch_cross_borrow_rate(&self, code: &str) -> Result<BorrowRate> {
+        // https://bybit-exchange.github.io/docs/v5/account/collateral-info
+        let result = self.private_get(self.api.collateral_info, &[("currency", code)]).await?;
+        let info = result.pointer("/list/0").context("no collateral info")?;
+        let timestamp = timestamp();
+        Ok(BorrowRate {
+            currency: self.get_currency_code(code),
+            rate: info.get("hourlyBorrowRate").and_then(|v| v.a_o_p_f64()),
+            period: 60 * 60 * 1000,
+            timestamp: Some(timestamp),
+            datetime: iso_8601(timestamp),
+            info: info.clone(),
+        })
+    }
+
+    // hourly borrow rates of spot margin, without since bybit returns the last 7 days
+    pub async fn fetch_borrow_rate_history(
+        &self,
+        code: &str,
+        since: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<BorrowRate>> {
+        // https://bybit-exchange.github.io/docs/v5/spot-margin-uta/historical-interest
+        let query = [("currency", code.to_string())];
+        let list = self
+            .fetch_paged(
+                PagedApi::private(self.api.borrow_rate_history, 50).window(30 * DAY, "timestamp"),
+                &query,
+                since,
+                None,
+                limit,
+            )
+            .await?;
+        let mut res = list
+            .iter()
+            .map(|rate| {
+                let timestamp = rate.get("timestamp").and_then(|v| v.a_o_p_i64());
+                BorrowRate {
+                    currency: self.get_currency_code(code),
+                    rate: rate.get("hourlyBorrowRate").and_then(|v| v.a_o_p_f64()),
+                    period: 60 * 60 * 1000,
+                    timestamp,
+                    datetime: timestamp.and_then(iso_8601),
+                    info: rate.clone(),
+                }
+            })
+            .collect::<Vec<_>>();
+        res.sort_by_key(|v| v.timestamp);
+        filter_by_limit(&mut res, limit, since.is_some());
+        Ok(res)
+    }
+
+    // hourly interest of unified account, without since bybit returns the last 30 days
+    pub async fn fetch_borrow_interest(
+        &self,
+        code: Option<&str>,
+        since: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<BorrowInterest>> {
+        // https://bybit-exchange.github.io/docs/v5/account/borrow-history
+        let query = code.map(|v| ("currency", v.to_string())).into_iter().collect::<Vec<_>>();
+        let list = self
+            .fetch_paged(
+                PagedApi::private(self.api.borrow_history, 50).window(30 * DAY, "createdTime"),
+                &query,
+                since,
+                None,
+                limit,
+            )
+            .await?;
+        let mut res = list
+            .iter()
+            .filter_map(|interest| {
+                let timestamp = interest.get("createdTime").and_then(|v| v.a_o_p_i64());
+                Some(BorrowInterest {
+                    currency: self.get_currency_code(interest.get("currency").and_then(|v| v.as_str())?),
+                    interest: interest.get("borrowCost").and_then(|v| v.a_o_p_f64()),
+                    interest_rate: interest.get("hourlyBorrowRate").and_then(|v| v.a_o_p_f64()),
+                    amount_borrowed: interest.get("InterestBearingBorrowSize").and_then(|v| v.a_o_p_f64()),
+                    margin_mode: "cross".to_string(),
+                    timestamp,
+                    datetime: timestamp.and_then(iso_8601),
+                    info: interest.clone(),
+                })
+            })
+            .collect::<Vec<_>>();
+        res.sort_by_key(|v| v.timestamp);
+        Ok(res)
+    }
+
+    // manual borrow of unified account
+    pub async fn borrow_cross_margin(&self, code: &str, amount: f64) -> Result<MarginLoan> {
+        // https://bybit-exchange.github.io/docs/v5/account/borrow
+        let body = serde_json::json!({
+            "coin": code,
+            "amount": self.currency_to_precision(code, amount, None).await?,
+        });
+        let result = self.private_post(self.api.borrow, &body).await?;
+        let amount = result.get("amount").and_then(|v| v.a_o_p_f64()).or(Some(amount));
+        Ok(self.margin_loan(code, amount, result))
+    }
+
+    // manual repay of unified account, repays all liabilities of code without amount
+    pub async fn repay_cross_margin(&self, code: &str, amount: Option<f64>) -> Result<MarginLoan> {
+        // https://bybit-exchange.github.io/docs/v5/account/repay
+        let mut body = serde_json::json!({
+            "coin": code,
+        });
+        if let Some(amount) = amount {
+            body["amount"] = self.currency_to_precision(code, amount, None).await?.into();
+        }
+        let result = self.private_post(self.api.repay, &body).await?;
+        // resultStatus: P processing, SU success, FA failed
+        anyhow::ensure!(
+            result.get("resultStatus").and_then(|v| v.as_str()) != Some("FA"),
+            "repay {code} failed: {result}"
+        );
+        Ok(self.margin_loan(code, amount, result))
+    }
+
+    fn margin_loan(&self, code: &str, amount: Option<f64>, info: Value) -> MarginLoan {
+        let timestamp = timestamp();
+        MarginLoan {
+            id: None,
+            currency: self.get_currency_code(code),
+            amount,
+            symbol: None,
+            timestamp: Some(timestamp),
+            datetime: iso_8601(timestamp),
+            info,
+        }
+    }
+
+    // code -> currencies which can be converted from with BybitOptions::convert_account_type
+    pub async fn fetch_convert_currencies(&self) -> Result<HashMap<String, Curreny>> {
+        // https://bybit-exchange.github.io/docs/v5/asset/convert/convert-coin-list
+        let query = [
+            ("accountType", self.option.convert_account_type.as_str()),
+            ("side", "0"),
+        ];
+        let result = self.private_get(self.api.convert_coins, &query).await?;
+        let coins = result.get("coins").and_then(|v| v.as_array()).context("no coins")?;
+        let mut res = HashMap::new();
+        for coin in coins {
+            let Some(id) = coin.get("coin").and_then(|v| v.as_str()) else {
+                continue;
+            };
+            let code = self.get_currency_code(id);
+            let get_f64 = |key: &str| coin.get(key).and_then(|v| v.a_o_p_f64());
+            let currency = Curreny {
+                id: id.to_string(),
+                code: code.clone(),
+                name: coin.get("fullName").and_then(|v| v.as_str()).unwrap_or(id).to_string(),
+                active: coin.get("disableFrom").and_then(|v| v.as_bool()).map(|v| !v),
+                precision: coin.get("accuracyLength").and_then(|v| v.a_o_p_i64()),
+                limits: CurrenyLimits {
+                    amount: Limit {
+                        min: get_f64("singleFromMinLimit"),
+                        max: get_f64("singleFromMaxLimit"),
+                    },
+                    ..Default::default()
+                },
+                r#type: if coin.get("fiat").and_then(|v| v.as_bool()) == Some(true) {
+                    "fiat"
+                } else {
+                    "crypto"
+                }
+                .to_string(),
+                info: coin.clone(),
+                ..Default::default()
+            };
+            res.insert(code, currency);
+        }
+        Ok(res)
+    }
+
+    // quote of converting amount of from_code to to_code, valid until expiry
+    pub async fn fetch_convert_quote(&self, from_code: &str, to_code: &str, amount: f64) -> Result<Conversion> {
+        // https://bybit-exchange.github.io/docs/v5/asset/convert/apply-quote
+        // requestAmount is limited to accuracyLength of the coin
+        let currencies = self.fetch_convert_currencies().await?;
+        let precision = currencies.get(from_code).with_context(|| format!("{from_code} can't be converted"))?.precision;
+        let body = serde_json::json!({
+            "fromCoin": from_code,
+            "toCoin": to_code,
+            "requestCoin": from_code,
+            "requestAmount": to_precision(amount, precision.and_then(decimals_to_step), true),
+            "accountType": self.option.convert_account_type,
+        });
+        let result = self.private_post(self.api.convert_quote, &body).await?;
+        self.parse_conversion(&result).context("invalid quote")
+    }
+
+    // executes quote, fails without request when quote has expired
+    pub async fn create_convert_trade(&self, quote: &Conversion) -> Result<Conversion> {
+        // https://bybit-exchange.github.io/docs/v5/asset/convert/confirm-quote
+        let expiry = quote.expiry.context("no expiry of quote")?;
+        anyhow::ensure!(timestamp() < expiry, "quote {} expired at {expiry}", quote.id);
+        let body = serde_json::json!({
+            "quoteTxId": quote.id,
+        });
+        let result = self.private_post(self.api.convert_execute, &body).await?;
+        let status = result.get("exchangeStatus").and_then(|v| v.as_str());
+        anyhow::ensure!(status != Some("failure"), "convert {} failed: {result}", quote.id);
+        let timestamp = timestamp();
+        Ok(Conversion {
+            id: quote.id.clone(),
+            timestamp: Some(timestamp),
+            datetime: iso_8601(timestamp),
+            from_currency: quote.from_currency.clone(),
+            from_amount: quote.from_amount,
+            to_currency: quote.to_currency.clone(),
+            to_amount: quote.to_amount,
+            price: quote.price,
+            fee: None,
+            expiry: quote.expiry,
+            status: status.map(|v| convert_status(v).to_string()),
+            info: result,
+        })
+    }
+
+    pub async fn fetch_convert_trade(&self, id: &str) -> Result<Conversion> {
+        // https://bybit-exchange.github.io/docs/v5/asset/convert/get-convert-result
+        let query = [
+            ("quoteTxId", id),
+            ("accountType", self.option.convert_account_type.as_str()),
+        ];
+        let result = self.private_get(self.api.convert_result, &query).await?;
+        let conversion = result.get("result").context("no result")?;
+        self.parse_conversion(conversion).with_context(|| format!("convert {id} not found"))
+    }
+
+    // newest first from bybit, code matches either side of the conversion
+    pub async fn fetch_convert_trade_history(
+        &self,
+        code: Option<&str>,
+        since: Option<i64>,
+        limit: Option<i64>,
+    ) -> Result<Vec<Conversion>> {
+        // https://bybit-exchange.github.io/docs/v5/asset/convert/get-convert-history
+        const PAGE_LIMIT: i64 = 100;
+        let mut res = Vec::new();
+        let mut index = 1;
+        'pages: loop {
+            let query = [
+                ("accountType", self.option.convert_account_type.clone()),
+                ("index", index.to_string()),
+                ("limit", PAGE_LIMIT.to_string()),
+            ];
+            let query = query.iter().map(|(k, v)| (*k, v.as_str())).collect::<Vec<_>>();
+            let result = self.private_get(self.api.convert_history, &query).await?;
+            let list = result.get("list").and_then(|v| v.as_array()).context("no list")?;
+            for conversion in list {
+                let Some(conversion) = self.parse_conversion(conversion) else {
+                    continue;
+                };
+                if since.is_some_and(|since| conversion.timestamp.is_some_and(|v| v < since)) {
+                    break 'pages;
+                }
+                if code.is_some_and(|code| code != conversion.from_currency && code != conversion.to_currency) {
+                    continue;
+                }
+                res.push(conversion);
+                if limit.is_some_and(|limit| res.len() as i64 >= limit) {
+                    break 'pages;
+                }
+            }
+            if (list.len() as i64) < PAGE_LIMIT {
+                break;
+            }
+            index += 1;
+        }
+        res.sort_by_key(|v| v.timestamp);
+        Ok(res)
+    }
+
+    // quote, execution result and history share the fields
+    fn parse_conversion(&self, conversion: &Value) -> Option<Conversion> {
+        let get_str = |key: &str| conversion.get(key).and_then(|v| v.as_str()).filter(|v| !v.is_empty());
+        let get_f64 = |key: &str| conversion.get(key).and_then(|v| v.a_o_p_f64());
+        let timestamp = conversion.get("createdAt").and_then(|v| v.a_o_p_i64());
+        Some(Conversion {
+            id: get_str("quoteTxId").or(get_str("exchangeTxId"))?.to_string(),
+            timestamp,
+            datetime: timestamp.and_then(iso_8601),
+            from_currency: self.get_currency_code(get_str("fromCoin")?),
+            from_amount: get_f64("fromAmount"),
+            to_currency: self.get_currency_code(get_str("toCoin")?),
+            to_amount: get_f64("toAmount"),
+            price: get_f64("exchangeRate").or(get_f64("convertRate")),
+            fee: None,
+            expiry: conversion.get("expiredTime").and_then(|v| v.a_o_p_i64()),
+            status: get_str("exchangeStatus").map(|v| convert_status(v).to_string()),
+            info: conversion.clone(),
+        })
+    }
+
+    // amount truncated to minAccuracy of the chain of network, of the finest chain of code without network
+    async fn currency_to_precision(&self, code: &str, amount: f64, network: Option<&str>) -> Result<String> {
+        let currencies = self.fetch_currencies().await?;
+        let currency = currencies.get(code).with_context(|| format!("currency {code} not found"))?;
+        let precision = match network {
+            Some(network) => {
+                let chain = currency.networks.get(network);
+                chain.with_context(|| format!("{code} has no network {network}"))?.precision
+            }
+            None => currency.precision,
+        };
+        Ok(to_precision(amount, precision.and_then(decimals_to_step), true))
+    }
+
+    // unified network code like ERC20 -> bybit chain like ETH
+    fn network_id<'a>(&'a self, network: &'a str) -> &'a str {
+        self.option.networks.get(network).map_or(network, |v| v)
+    }
+
+    // bybit chain like ETH -> unified network code like ERC20, networks_by_id only holds
+    // the chains shared by several codes, the others are looked up reversely in networks
+    fn network_code<'a>(&'a self, network_id: &'a str) -> &'a str {
+        if let Some(code) = self.option.networks_by_id.get(network_id) {
+            return code;
+        }
+        let code = self.option.networks.iter().find(|(_, v)| *v == network_id);
+        code.map_or(network_id, |(k, _)| k)
+    }
+
+    // ccxt account name or bybit account name -> bybit account name
+    fn account_type<'a>(&'a self, account_type: &'a str) -> Result<&'a str> {
+        if let Some(account_type) = self.option.account_by_type.get(account_type) {
+            return Ok(account_type);
+        }
+        anyhow::ensure!(
+            self.option.account_by_id.contains_key(account_type),
+            "unknown account type {account_type}"
+        );
+        Ok(account_type)
+    }
+
+    // bybit account name -> ccxt account name
+    fn account_name<'a>(&'a self, account_type: &'a str) -> &'a str {
+        self.option.account_by_id.get(account_type).map_or(account_type, |v| v)
+    }
+
+    // body of a single order without category, shared by create_order and create_orders
+    fn order_request_body(&self, request: &OrderRequest, market: &Market) -> Result<serde_json::Map<String, Value>> {
+        let mut body = serde_json::Map::new();
+        body.insert("symbol".to_string(), market.id.clone().into());
+        let side = match request.side.as_str() {
+            "buy" => "Buy",
+            "sell" => "Sell",
+            side => anyhow::bail!("invalid order side {side}"),
+        };
+        body.insert("side".to_string(), side.into());
+        let is_market = match request.r#type.as_str() {
+            "market" => true,
+            "limit" => false,
+            order_type => anyhow::bail!("invalid order type {order_type}"),
+        };
+        body.insert(
+            "orderType".to_string(),
+            if is_market { "Market" } else { "Limit" }.into(),
+        );
+
+        if let Some(cost) = request.cost {
+            anyhow::ensure!(
+                market.spot && is_market,
+                "order by cost is only supported by spot market order"
+            );
+            let quote_precision = market.info.pointer("/lotSizeFilter/quotePrecision").and_then(|v| v.a_o_p_f64());
+            body.insert("qty".to_string(), to_precision(cost, quote_precision, true).into());
+            body.insert("marketUnit".to_string(), "quoteCoin".into());
+        } else {
+            body.insert(
+                "qty".to_string(),
+                to_precision(request.amount, market.precision.amount, true).into(),
+            );
+            if market.spot && is_market {
+                body.insert("marketUnit".to_string(), "baseCoin".into());
+            }
+        }
+        if !is_market {
+            let price = request.price.context("price is required by limit order")?;
+            body.insert(
+                "price".to_string(),
+                to_precision(price, market.precision.price, false).into(),
+            );
+        }
+
+        let time_in_force = if request.post_only {
+            Some("PostOnly")
+        } else {
+            match request.time_in_force.as_deref() {
+                Some("GTC") => Some("GTC"),
+                Some("IOC") => Some("IOC"),
+                Some("FOK") => Some("FOK"),
+                Some("PO") => Some("PostOnly"),
+                Some(time_in_force) => anyhow::bail!("invalid time in force {time_in_force}"),
+                None => None,
+            }
+        };
+        if let Some(time_in_force) = time_in_force {
+            body.insert("timeInForce".to_string(), time_in_force.into());
+        }
+        if request.reduce_only {
+            body.insert("reduceOnly".to_string(), true.into());
+        }
+        if let Some(client_order_id) = request.client_order_id.as_deref() {
+            body.insert("orderLinkId".to_string(), client_order_id.into());
+        }
+        if let Some(trigger_price) = request.trigger_price {
+            body.insert(
+                "triggerPrice".to_string(),
+                to_precision(trigger_price, market.precision.price, false).into(),
+            );
+            if market.spot {
+                body.insert("orderFilter".to_string(), "StopOrder".into());
+            } else {
+                // 1: triggered when price rises to trigger price, 2: falls to
+                let direction = match request.trigger_direction.as_deref() {
+                    Some("above") => 1,
+                    Some("below") => 2,
+                    Some(direction) => anyhow::bail!("invalid trigger direction {direction}"),
+                    None => anyhow::bail!("trigger direction is required by contract trigger order"),
+                };
+                body.insert("triggerDirection".to_string(), direction.into());
+            }
+        }
+        if let Some(take_profit) = request.take_profit_price {
+            body.insert(
+                "takeProfit".to_string(),
+                to_precision(take_profit, market.precision.price, false).into(),
+            );
+        }
+        if let Some(stop_loss) = request.stop_loss_price {
+            body.insert(
+                "stopLoss".to_string(),
+                to_precision(stop_loss, market.precision.price, false).into(),
+            );
+        }
+        if let Some(position_idx) = request.position_idx {
+            body.insert("positionIdx".to_string(), position_idx.into());
+        }
+        Ok(body)
+    }
+
+    // create and amend endpoints only return ids, the rest is filled from the request
+    fn fill_order_from_request(&self, mut order: Order, request: &OrderRequest) -> Order {
+        order.r#type = Some(request.r#type.clone());
+        order.side = Some(request.side.clone());
+        // amount is ignored when ordering by cost, and 0 keeps the original one when amending
+        order.amount = Some(request.amount).filter(|v| request.cost.is_none() && *v > 0.0);
+        order.cost = order.cost.or(request.cost);
+        order.price = order.price.or(request.price);
+        order.time_in_force = order.time_in_force.or(request.time_in_force.clone());
+        order.post_only = Some(request.post_only);
+        order.reduce_only = Some(request.reduce_only);
+        order.trigger_price = order.trigger_price.or(request.trigger_price);
+        order.take_profit_price = order.take_profit_price.or(request.take_profit_price);
+        order.stop_loss_price = order.stop_loss_price.or(request.stop_loss_price);
+        order
+    }
+
+    fn parse_order(&self, order: &Value, market: &Market) -> Order {
+        let get_str = |key: &str| order.get(key).and_then(|v| v.as_str()).filter(|v| !v.is_empty());
+        // bybit returns 0 for unset prices
+        let get_f64 = |key: &str| order.get(key).and_then(|v| v.a_o_p_f64()).filter(|v| *v != 0.0);
+        let timestamp = order.get("createdTime").or(order.get("createAt")).and_then(|v| v.a_o_p_i64());
+        let last_update_timestamp = order.get("updatedTime").and_then(|v| v.a_o_p_i64());
+        let filled = order.get("cumExecQty").and_then(|v| v.a_o_p_f64());
+        let time_in_force = get_str("timeInForce").map(|v| if v == "PostOnly" { "PO" } else { v }.to_string());
+        let fee = order.get("cumExecFee").and_then(|v| v.a_o_p_f64()).map(|cost| {
+            let currency = if market.spot {
+                if get_str("side") == Some("Buy") {
+                    Some(market.base.clone())
+                } else {
+                    Some(market.quote.clone())
+                }
+            } else {
+                market.settle.clone()
+            };
+            Fee {
+                cost: Some(cost),
+                currency,
+                rate: None,
+            }
+        });
+        Order {
+            id: get_str("orderId").unwrap_or_default().to_string(),
+            client_order_id: get_str("orderLinkId").map(|v| v.to_string()),
+            timestamp,
+            datetime: timestamp.and_then(iso_8601),
+            last_trade_timestamp: None,
+            last_update_timestamp,
+            status: get_str("orderStatus").map(|v| order_status(v).to_string()),
+            symbol: market.symbol.clone(),
+            r#type: get_str("orderType").map(|v| v.to_lowercase()),
+            post_only: time_in_force.as_ref().map(|v| v == "PO"),
+            time_in_force,
+            side: get_str("side").map(|v| v.to_lowercase()),
+            price: get_f64("price"),
+            average: get_f64("avgPrice"),
+            amount: get_f64("qty"),
+            filled,
+            remaining: order.get("leavesQty").and_then(|v| v.a_o_p_f64()),
+            cost: order.get("cumExecValue").and_then(|v| v.a_o_p_f64()),
+            trigger_price: get_f64("triggerPrice"),
+            take_profit_price: get_f64("takeProfit"),
+            stop_loss_price: get_f64("stopLoss"),
+            reduce_only: order.get("reduceOnly").and_then(|v| v.as_bool()),
+            trades: Vec::new(),
+            fee,
+            info: order.clone(),
+        }
+    }
+
+    // GET, returns result of the response
+    async fn public_get(&self, path: &str, query: &[(&str, &str)]) -> Result<Value> {
+        let url = self.new_url(path)?;
+        let mut resp = self.http_client.get(url).query(query).send().await?.json::<Value>().await?;
+        self.check_resp(&resp)?;
+        Ok(resp.get_mut("result").context("no result")?.take())
+    }
+
+    // signed GET, returns result of the response
+    async fn private_get(&self, path: &str, query: &[(&str, &str)]) -> Result<Value> {
+        let url = self.new_url(path)?;
+        let mut resp = self.sign_requst(self.http_client.get(url).query(query)).send().await?.json::<Value>().await?;
+        self.check_resp(&resp)?;
+        Ok(resp.get_mut("result").context("no result")?.take())
+    }
+
+    // signed POST, returns result of the response
+    async fn private_post(&self, path: &str, body: &Value) -> Result<Value> {
+        let mut resp = self.private_post_raw(path, body).await?;
+        Ok(resp.get_mut("result").context("no result")?.take())
+    }
+
+    // signed POST, returns the whole response for retExtInfo
+    async fn private_post_raw(&self, path: &str, body: &Value) -> Result<Value> {
+        self.private_post_not_modified(path, body, &[]).await
+    }
+
+    // signed POST of setters, ret codes of not_modified mean the value is already set and are taken as success
+    async fn private_post_not_modified(&self, path: &str, body: &Value, not_modified: &[i64]) -> Result<Value> {
+        let url = self.new_url(path)?;
+        let resp = self.sign_requst(self.http_client.post(url).json(body)).send().await?.json::<Value>().await?;
+        if resp.get("retCode").and_then(|v| v.a_o_p_i64()).is_some_and(|v| not_modified.contains(&v)) {
+            return Ok(resp);
+        }
+        self.check_resp(&resp)?;
+        Ok(resp)
+    }
+
     // TODO
     fn get_currency_code(&self, id: &str) -> String {
         id.to_string()
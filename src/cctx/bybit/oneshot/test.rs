@@ -1,7 +1,7 @@
 use super::*;
 use std::sync::LazyLock;
 
-static BYBIT: LazyLock<Bybit> = LazyLock::new(|| test_bybit());
+static BYBIT: LazyLock<Bybit> = LazyLock::new(test_bybit);
 
 fn test_bybit() -> Bybit {
     Bybit::new("123", "456").unwrap()
@@ -28,3 +28,156 @@ async fn test_fetch_spot_markets() {
     // let spot_markets = spot_markets.unwrap();
     println!("next is {:#?}", spot_markets);
 }
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fetch_markets() {
+    let markets = BYBIT.fetch_markets(&[]).await;
+    assert!(markets.is_ok());
+    let markets = markets.unwrap();
+    assert!(markets.iter().any(|m| m.option && m.strike.is_some() && m.option_type.is_some()));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_load_markets() {
+    let markets = BYBIT.load_markets(false).await;
+    assert!(markets.is_ok());
+    let market = BYBIT.market("BTC/USDT:USDT");
+    assert!(market.is_ok());
+    let market = market.unwrap();
+    assert!(market.id == "BTCUSDT");
+    assert!(BYBIT.market_by_id("BTCUSDT", "linear").is_ok_and(|m| m.symbol == market.symbol));
+    assert!(BYBIT.market_by_id("BTCUSDT", "spot").is_ok_and(|m| m.symbol == "BTC/USDT"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fetch_ticker() {
+    let ticker = BYBIT.fetch_ticker("BTC/USDT:USDT").await;
+    assert!(ticker.is_ok());
+    println!("{ticker:#?}");
+    let tickers = BYBIT.fetch_tickers(&["BTC/USDT".to_string(), "ETH/USDT:USDT".to_string()]).await;
+    assert!(tickers.is_ok_and(|v| v.len() == 2));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fetch_order_book() {
+    let order_book = BYBIT.fetch_order_book("BTC/USDT", Some(10)).await;
+    assert!(order_book.is_ok());
+    let order_book = order_book.unwrap();
+    assert!(order_book.bids.len() <= 10 && order_book.asks.len() <= 10);
+    assert!(order_book.bids.first().map(|v| v.0) < order_book.asks.first().map(|v| v.0));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fetch_trades() {
+    let trades = BYBIT.fetch_trades("BTC/USDT:USDT", None, Some(100)).await;
+    assert!(trades.is_ok_and(|v| !v.is_empty() && v.len() <= 100));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fetch_ohlcv() {
+    let since = BYBIT.fetch_time().await.unwrap() - 3 * 24 * 3600 * 1000;
+    let ohlcv = BYBIT.fetch_ohlcv("BTC/USDT", "1m", Some(since), None).await;
+    assert!(ohlcv.is_ok());
+    let ohlcv = ohlcv.unwrap();
+    assert!(ohlcv.len() > 1000);
+    assert!(ohlcv.windows(2).all(|v| v[0].timestamp + 60_000 == v[1].timestamp));
+}
+
+#[test]
+fn test_account_type() {
+    assert!(BYBIT.account_type("funding").is_ok_and(|v| v == "FUND"));
+    assert!(BYBIT.account_type("swap").is_ok_and(|v| v == "CONTRACT"));
+    assert!(BYBIT.account_type("UNIFIED").is_ok_and(|v| v == "UNIFIED"));
+    assert!(BYBIT.account_type("wallet").is_err());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fetch_funding_rate_history() {
+    let since = BYBIT.fetch_time().await.unwrap() - 180 * 24 * 3600 * 1000;
+    let history = BYBIT.fetch_funding_rate_history("BTC/USDT:USDT", Some(since), None).await;
+    assert!(history.is_ok());
+    let history = history.unwrap();
+    assert!(history.len() > 200);
+    assert!(history.first().is_some_and(|v| v.timestamp >= since));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fetch_open_interest_history() {
+    let history = BYBIT.fetch_open_interest_history("BTC/USDT:USDT", "1h", None, None, Some(300)).await;
+    assert!(history.is_ok());
+    let history = history.unwrap();
+    assert!(history.len() == 300);
+    assert!(history.windows(2).all(|v| v[0].timestamp < v[1].timestamp));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_fetch_option_chain() {
+    let chain = BYBIT.fetch_option_chain("BTC").await;
+    assert!(chain.is_ok());
+    let chain = chain.unwrap();
+    assert!(chain.values().all(|v| v.strike.is_some() && v.expiry.is_some()));
+    let symbol = chain.keys().next().unwrap();
+    assert!(BYBIT.fetch_greeks(symbol).await.is_ok_and(|v| v.delta.is_some()));
+}
+
+#[test]
+fn test_network() {
+    assert!(BYBIT.network_id("ERC20") == "ETH");
+    assert!(BYBIT.network_code("ETH") == "ERC20");
+    assert!(BYBIT.network_code("TRX") == "TRC20");
+    assert!(BYBIT.network_id("ARBONE") == "ARBI");
+    assert!(BYBIT.network_code("ARBI") == "ARBONE");
+    assert!(BYBIT.network_code("CAVAX") == "AVAXC");
+    assert!(BYBIT.network_id("UNKNOWN") == "UNKNOWN");
+    assert!(BYBIT.network_code("UNKNOWN") == "UNKNOWN");
+}
+
+#[test]
+fn test_account_name() {
+    assert!(BYBIT.account_name("FUND") == "fund");
+    assert!(BYBIT.account_name("UNIFIED") == "unified");
+    assert!(BYBIT.account_name("UNKNOWN") == "UNKNOWN");
+}
+
+#[test]
+fn test_leverage_tiers() {
+    let rows = serde_json::json!([
+        {"riskLimitValue": "400000", "maintenanceMargin": "0.01", "mmDeduction": "0", "initialMargin": "0.02", "maxLeverage": "50.00"},
+        {"riskLimitValue": "200000", "maintenanceMargin": "0.005", "mmDeduction": "", "initialMargin": "0.01", "maxLeverage": "100.00"},
+    ]);
+    let market = Market {
+        symbol: "BTC/USDT:USDT".to_string(),
+        ..Default::default()
+    };
+    let tiers = BYBIT.parse_market_leverage_tiers(rows.as_array().unwrap(), &market);
+    assert!(tiers.len() == 2);
+    assert!(tiers[0].tier == 1 && tiers[0].min_notional == 0.0 && tiers[0].max_notional == 200000.0);
+    assert!(tiers[1].min_notional == 200000.0 && tiers[1].max_leverage == Some(50.0));
+
+    let margin = LeverageTier::margin_requirement(&tiers, 100000.0, None).unwrap();
+    assert!(margin.tier == 1 && margin.leverage == 100.0);
+    assert!(margin.initial_margin == 1000.0 && margin.maintenance_margin == 500.0);
+    let margin = LeverageTier::margin_requirement(&tiers, -300000.0, Some(100.0)).unwrap();
+    assert!(margin.tier == 2 && margin.leverage == 50.0 && margin.initial_margin == 6000.0);
+    assert!(LeverageTier::margin_requirement(&tiers, 500000.0, None).is_none());
+    assert!(LeverageTier::margin_requirement(&tiers, 100000.0, Some(0.0)).is_none());
+    assert!(LeverageTier::margin_requirement(&tiers, 100000.0, Some(-5.0)).is_none());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+async fn test_create_convert_trade_expired() {
+    let quote = BYBIT
+        .parse_conversion(&serde_json::json!({
+            "quoteTxId": "10100108106409343501030232064",
+            "exchangeRate": "0.0000152",
+            "fromCoin": "USDT",
+            "toCoin": "BTC",
+            "fromAmount": "10",
+            "toAmount": "0.000152",
+            "expiredTime": "1700000015000",
+        }))
+        .unwrap();
+    assert!(quote.expiry == Some(1700000015000) && quote.price == Some(0.0000152));
+    let res = BYBIT.create_convert_trade(&quote).await;
+    assert!(res.is_err_and(|e| e.to_string().contains("expired")));
+}
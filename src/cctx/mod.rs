@@ -1,5 +1,8 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 
+use anyhow::Result;
+use futures::future::BoxFuture;
 use serde::Serialize;
 use serde_json::Value;
 
@@ -47,28 +50,1128 @@ where
 {
 }
 
-#[derive(Debug, Serialize)]
+pub type ExchangeFuture<'a, T> = BoxFuture<'a, Result<T>>;
+
+// unified interface over exchanges, follows the method names of ccxt
+// methods an exchange does not implement fail with "not supported",
+// `has` tells which ones are available without calling them
+pub trait Exchange: Send + Sync {
+    fn id(&self) -> &'static str;
+
+    // ccxt names of the methods implemented by this exchange
+    fn implemented(&self) -> &'static [&'static str];
+
+    fn has(&self) -> HashMap<&'static str, bool> {
+        let implemented = self.implemented();
+        CAPABILITIES.iter().map(|&name| (name, implemented.contains(&name))).collect()
+    }
+
+    fn fetch_time(&self) -> ExchangeFuture<'_, i64> {
+        not_supported("fetchTime")
+    }
+
+    fn fetch_currencies(&self) -> ExchangeFuture<'_, HashMap<String, Curreny>> {
+        not_supported("fetchCurrencies")
+    }
+
+    fn load_markets(&self, _reload: bool) -> ExchangeFuture<'_, HashMap<String, Arc<Market>>> {
+        not_supported("loadMarkets")
+    }
+
+    fn fetch_ticker<'a>(&'a self, _symbol: &'a str) -> ExchangeFuture<'a, Ticker> {
+        not_supported("fetchTicker")
+    }
+
+    fn fetch_tickers<'a>(&'a self, _symbols: &'a [String]) -> ExchangeFuture<'a, HashMap<String, Ticker>> {
+        not_supported("fetchTickers")
+    }
+
+    fn fetch_bids_asks<'a>(&'a self, _symbols: &'a [String]) -> ExchangeFuture<'a, HashMap<String, Ticker>> {
+        not_supported("fetchBidsAsks")
+    }
+
+    fn fetch_order_book<'a>(&'a self, _symbol: &'a str, _limit: Option<i64>) -> ExchangeFuture<'a, OrderBook> {
+        not_supported("fetchOrderBook")
+    }
+
+    fn fetch_trades<'a>(
+        &'a self,
+        _symbol: &'a str,
+        _since: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<Trade>> {
+        not_supported("fetchTrades")
+    }
+
+    fn fetch_ohlcv<'a>(
+        &'a self,
+        _symbol: &'a str,
+        _timeframe: &'a str,
+        _since: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<OHLCV>> {
+        not_supported("fetchOHLCV")
+    }
+
+    fn fetch_funding_rates<'a>(&'a self, _symbols: &'a [String]) -> ExchangeFuture<'a, HashMap<String, FundingRate>> {
+        not_supported("fetchFundingRates")
+    }
+
+    fn fetch_funding_rate_history<'a>(
+        &'a self,
+        _symbol: &'a str,
+        _since: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<FundingRateHistory>> {
+        not_supported("fetchFundingRateHistory")
+    }
+
+    fn fetch_open_interest<'a>(&'a self, _symbol: &'a str) -> ExchangeFuture<'a, OpenInterest> {
+        not_supported("fetchOpenInterest")
+    }
+
+    fn fetch_open_interest_history<'a>(
+        &'a self,
+        _symbol: &'a str,
+        _timeframe: &'a str,
+        _since: Option<i64>,
+        _until: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<OpenInterest>> {
+        not_supported("fetchOpenInterestHistory")
+    }
+
+    fn fetch_long_short_ratio_history<'a>(
+        &'a self,
+        _symbol: &'a str,
+        _timeframe: &'a str,
+        _since: Option<i64>,
+        _until: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<LongShortRatio>> {
+        not_supported("fetchLongShortRatioHistory")
+    }
+
+    fn fetch_volatility_history<'a>(
+        &'a self,
+        _code: &'a str,
+        _period: Option<i64>,
+        _since: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<Volatility>> {
+        not_supported("fetchVolatilityHistory")
+    }
+
+    fn fetch_greeks<'a>(&'a self, _symbol: &'a str) -> ExchangeFuture<'a, Greeks> {
+        not_supported("fetchGreeks")
+    }
+
+    fn fetch_all_greeks<'a>(&'a self, _symbols: &'a [String]) -> ExchangeFuture<'a, HashMap<String, Greeks>> {
+        not_supported("fetchAllGreeks")
+    }
+
+    fn fetch_option<'a>(&'a self, _symbol: &'a str) -> ExchangeFuture<'a, OptionContract> {
+        not_supported("fetchOption")
+    }
+
+    fn fetch_option_chain<'a>(&'a self, _underlying: &'a str) -> ExchangeFuture<'a, HashMap<String, OptionContract>> {
+        not_supported("fetchOptionChain")
+    }
+
+    fn create_order<'a>(&'a self, _request: &'a OrderRequest) -> ExchangeFuture<'a, Order> {
+        not_supported("createOrder")
+    }
+
+    fn create_orders<'a>(&'a self, _requests: &'a [OrderRequest]) -> ExchangeFuture<'a, Vec<Order>> {
+        not_supported("createOrders")
+    }
+
+    fn create_market_buy_order_with_cost<'a>(&'a self, _symbol: &'a str, _cost: f64) -> ExchangeFuture<'a, Order> {
+        not_supported("createMarketBuyOrderWithCost")
+    }
+
+    fn create_market_sell_order_with_cost<'a>(&'a self, _symbol: &'a str, _cost: f64) -> ExchangeFuture<'a, Order> {
+        not_supported("createMarketSellOrderWithCost")
+    }
+
+    fn edit_order<'a>(&'a self, _id: &'a str, _request: &'a OrderRequest) -> ExchangeFuture<'a, Order> {
+        not_supported("editOrder")
+    }
+
+    fn edit_orders<'a>(&'a self, _orders: &'a [(String, OrderRequest)]) -> ExchangeFuture<'a, Vec<Order>> {
+        not_supported("editOrders")
+    }
+
+    fn cancel_order<'a>(&'a self, _id: &'a str, _symbol: &'a str) -> ExchangeFuture<'a, Order> {
+        not_supported("cancelOrder")
+    }
+
+    fn cancel_orders<'a>(&'a self, _ids: &'a [String], _symbol: &'a str) -> ExchangeFuture<'a, Vec<Order>> {
+        not_supported("cancelOrders")
+    }
+
+    fn cancel_orders_for_symbols<'a>(&'a self, _orders: &'a [(String, String)]) -> ExchangeFuture<'a, Vec<Order>> {
+        not_supported("cancelOrdersForSymbols")
+    }
+
+    fn cancel_all_orders<'a>(&'a self, _symbol: Option<&'a str>) -> ExchangeFuture<'a, Vec<Order>> {
+        not_supported("cancelAllOrders")
+    }
+
+    fn cancel_all_orders_after<'a>(&'a self, _timeout: i64, _product: Option<&'a str>) -> ExchangeFuture<'a, ()> {
+        not_supported("cancelAllOrdersAfter")
+    }
+
+    fn fetch_balance<'a>(&'a self, _account_type: Option<&'a str>) -> ExchangeFuture<'a, Balances> {
+        not_supported("fetchBalance")
+    }
+
+    fn fetch_order<'a>(&'a self, _id: &'a str, _symbol: &'a str) -> ExchangeFuture<'a, Order> {
+        not_supported("fetchOrder")
+    }
+
+    fn fetch_open_orders<'a>(
+        &'a self,
+        _symbol: Option<&'a str>,
+        _since: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<Order>> {
+        not_supported("fetchOpenOrders")
+    }
+
+    fn fetch_closed_orders<'a>(
+        &'a self,
+        _symbol: Option<&'a str>,
+        _since: Option<i64>,
+        _until: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<Order>> {
+        not_supported("fetchClosedOrders")
+    }
+
+    fn fetch_canceled_orders<'a>(
+        &'a self,
+        _symbol: Option<&'a str>,
+        _since: Option<i64>,
+        _until: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<Order>> {
+        not_supported("fetchCanceledOrders")
+    }
+
+    fn fetch_canceled_and_closed_orders<'a>(
+        &'a self,
+        _symbol: Option<&'a str>,
+        _since: Option<i64>,
+        _until: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<Order>> {
+        not_supported("fetchCanceledAndClosedOrders")
+    }
+
+    fn fetch_order_trades<'a>(
+        &'a self,
+        _id: &'a str,
+        _symbol: &'a str,
+        _since: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<Trade>> {
+        not_supported("fetchOrderTrades")
+    }
+
+    fn fetch_my_trades<'a>(
+        &'a self,
+        _symbol: Option<&'a str>,
+        _since: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<Trade>> {
+        not_supported("fetchMyTrades")
+    }
+
+    fn fetch_position<'a>(&'a self, _symbol: &'a str) -> ExchangeFuture<'a, Position> {
+        not_supported("fetchPosition")
+    }
+
+    fn fetch_positions<'a>(&'a self, _symbols: &'a [String]) -> ExchangeFuture<'a, Vec<Position>> {
+        not_supported("fetchPositions")
+    }
+
+    fn fetch_positions_history<'a>(
+        &'a self,
+        _symbol: Option<&'a str>,
+        _since: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<Position>> {
+        not_supported("fetchPositionsHistory")
+    }
+
+    fn fetch_leverage<'a>(&'a self, _symbol: &'a str) -> ExchangeFuture<'a, Leverage> {
+        not_supported("fetchLeverage")
+    }
+
+    fn set_leverage<'a>(&'a self, _leverage: f64, _symbol: &'a str) -> ExchangeFuture<'a, ()> {
+        not_supported("setLeverage")
+    }
+
+    fn set_margin_mode<'a>(&'a self, _margin_mode: &'a str, _symbol: Option<&'a str>) -> ExchangeFuture<'a, ()> {
+        not_supported("setMarginMode")
+    }
+
+    fn set_position_mode<'a>(&'a self, _hedged: bool, _symbol: Option<&'a str>) -> ExchangeFuture<'a, ()> {
+        not_supported("setPositionMode")
+    }
+
+    fn fetch_deposit_address<'a>(
+        &'a self,
+        _code: &'a str,
+        _network: Option<&'a str>,
+    ) -> ExchangeFuture<'a, DepositAddress> {
+        not_supported("fetchDepositAddress")
+    }
+
+    fn fetch_deposit_addresses_by_network<'a>(
+        &'a self,
+        _code: &'a str,
+        _network: Option<&'a str>,
+    ) -> ExchangeFuture<'a, HashMap<String, DepositAddress>> {
+        not_supported("fetchDepositAddressesByNetwork")
+    }
+
+    fn fetch_deposits<'a>(
+        &'a self,
+        _code: Option<&'a str>,
+        _since: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<Transaction>> {
+        not_supported("fetchDeposits")
+    }
+
+    fn fetch_withdrawals<'a>(
+        &'a self,
+        _code: Option<&'a str>,
+        _since: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<Transaction>> {
+        not_supported("fetchWithdrawals")
+    }
+
+    fn withdraw<'a>(
+        &'a self,
+        _code: &'a str,
+        _amount: f64,
+        _address: &'a str,
+        _tag: Option<&'a str>,
+        _network: Option<&'a str>,
+    ) -> ExchangeFuture<'a, Transaction> {
+        not_supported("withdraw")
+    }
+
+    fn transfer<'a>(
+        &'a self,
+        _code: &'a str,
+        _amount: f64,
+        _from_account: &'a str,
+        _to_account: &'a str,
+    ) -> ExchangeFuture<'a, TransferEntry> {
+        not_supported("transfer")
+    }
+
+    fn fetch_transfers<'a>(
+        &'a self,
+        _code: Option<&'a str>,
+        _since: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<TransferEntry>> {
+        not_supported("fetchTransfers")
+    }
+
+    fn fetch_ledger<'a>(
+        &'a self,
+        _code: Option<&'a str>,
+        _since: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<LedgerEntry>> {
+        not_supported("fetchLedger")
+    }
+
+    fn fetch_cross_borrow_rate<'a>(&'a self, _code: &'a str) -> ExchangeFuture<'a, BorrowRate> {
+        not_supported("fetchCrossBorrowRate")
+    }
+
+    fn fetch_borrow_rate_history<'a>(
+        &'a self,
+        _code: &'a str,
+        _since: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<BorrowRate>> {
+        not_supported("fetchBorrowRateHistory")
+    }
+
+    fn fetch_borrow_interest<'a>(
+        &'a self,
+        _code: Option<&'a str>,
+        _since: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<BorrowInterest>> {
+        not_supported("fetchBorrowInterest")
+    }
+
+    fn borrow_cross_margin<'a>(&'a self, _code: &'a str, _amount: f64) -> ExchangeFuture<'a, MarginLoan> {
+        not_supported("borrowCrossMargin")
+    }
+
+    fn repay_cross_margin<'a>(&'a self, _code: &'a str, _amount: Option<f64>) -> ExchangeFuture<'a, MarginLoan> {
+        not_supported("repayCrossMargin")
+    }
+
+    fn fetch_leverage_tiers<'a>(
+        &'a self,
+        _symbols: &'a [String],
+    ) -> ExchangeFuture<'a, HashMap<String, Vec<LeverageTier>>> {
+        not_supported("fetchLeverageTiers")
+    }
+
+    fn fetch_market_leverage_tiers<'a>(&'a self, _symbol: &'a str) -> ExchangeFuture<'a, Vec<LeverageTier>> {
+        not_supported("fetchMarketLeverageTiers")
+    }
+
+    fn fetch_trading_fee<'a>(&'a self, _symbol: &'a str) -> ExchangeFuture<'a, TradingFee> {
+        not_supported("fetchTradingFee")
+    }
+
+    fn fetch_trading_fees(&self) -> ExchangeFuture<'_, HashMap<String, TradingFee>> {
+        not_supported("fetchTradingFees")
+    }
+
+    fn fetch_deposit_withdraw_fees<'a>(
+        &'a self,
+        _codes: &'a [String],
+    ) -> ExchangeFuture<'a, HashMap<String, DepositWithdrawFee>> {
+        not_supported("fetchDepositWithdrawFees")
+    }
+
+    fn fetch_convert_currencies(&self) -> ExchangeFuture<'_, HashMap<String, Curreny>> {
+        not_supported("fetchConvertCurrencies")
+    }
+
+    fn fetch_convert_quote<'a>(
+        &'a self,
+        _from_code: &'a str,
+        _to_code: &'a str,
+        _amount: f64,
+    ) -> ExchangeFuture<'a, Conversion> {
+        not_supported("fetchConvertQuote")
+    }
+
+    fn create_convert_trade<'a>(&'a self, _quote: &'a Conversion) -> ExchangeFuture<'a, Conversion> {
+        not_supported("createConvertTrade")
+    }
+
+    fn fetch_convert_trade<'a>(&'a self, _id: &'a str) -> ExchangeFuture<'a, Conversion> {
+        not_supported("fetchConvertTrade")
+    }
+
+    fn fetch_convert_trade_history<'a>(
+        &'a self,
+        _code: Option<&'a str>,
+        _since: Option<i64>,
+        _limit: Option<i64>,
+    ) -> ExchangeFuture<'a, Vec<Conversion>> {
+        not_supported("fetchConvertTradeHistory")
+    }
+}
+
+pub const CAPABILITIES: &[&str] = &[
+    "fetchTime",
+    "fetchCurrencies",
+    "loadMarkets",
+    "fetchTicker",
+    "fetchTickers",
+    "fetchBidsAsks",
+    "fetchOrderBook",
+    "fetchTrades",
+    "fetchOHLCV",
+    "fetchFundingRates",
+    "fetchFundingRateHistory",
+    "fetchOpenInterest",
+    "fetchOpenInterestHistory",
+    "fetchLongShortRatioHistory",
+    "fetchVolatilityHistory",
+    "fetchGreeks",
+    "fetchAllGreeks",
+    "fetchOption",
+    "fetchOptionChain",
+    "createOrder",
+    "createOrders",
+    "createMarketBuyOrderWithCost",
+    "createMarketSellOrderWithCost",
+    "editOrder",
+    "editOrders",
+    "cancelOrder",
+    "cancelOrders",
+    "cancelOrdersForSymbols",
+    "cancelAllOrders",
+    "cancelAllOrdersAfter",
+    "fetchBalance",
+    "fetchOrder",
+    "fetchOpenOrders",
+    "fetchClosedOrders",
+    "fetchCanceledOrders",
+    "fetchCanceledAndClosedOrders",
+    "fetchOrderTrades",
+    "fetchMyTrades",
+    "fetchPosition",
+    "fetchPositions",
+    "fetchPositionsHistory",
+    "fetchLeverage",
+    "setLeverage",
+    "setMarginMode",
+    "setPositionMode",
+    "fetchDepositAddress",
+    "fetchDepositAddressesByNetwork",
+    "fetchDeposits",
+    "fetchWithdrawals",
+    "withdraw",
+    "transfer",
+    "fetchTransfers",
+    "fetchLedger",
+    "fetchCrossBorrowRate",
+    "fetchBorrowRateHistory",
+    "fetchBorrowInterest",
+    "borrowCrossMargin",
+    "repayCrossMargin",
+    "fetchLeverageTiers",
+    "fetchMarketLeverageTiers",
+    "fetchTradingFee",
+    "fetchTradingFees",
+    "fetchDepositWithdrawFees",
+    "fetchConvertCurrencies",
+    "fetchConvertQuote",
+    "createConvertTrade",
+    "fetchConvertTrade",
+    "fetchConvertTradeHistory",
+];
+
+fn not_supported<'a, T: Send + 'a>(name: &'static str) -> ExchangeFuture<'a, T> {
+    Box::pin(async move { anyhow::bail!("{name} not supported") })
+}
+
+#[derive(Debug, Serialize, Default)]
 pub struct Ticker {
     pub symbol: String,
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    pub high: Option<f64>,
+    pub low: Option<f64>,
+    pub bid: Option<f64>,
+    pub bid_volume: Option<f64>,
+    pub ask: Option<f64>,
+    pub ask_volume: Option<f64>,
+    pub vwap: Option<f64>,
+    pub open: Option<f64>,
+    pub close: Option<f64>,
+    pub last: Option<f64>,
+    pub previous_close: Option<f64>,
+    pub change: Option<f64>,
+    pub percentage: Option<f64>,
+    pub average: Option<f64>,
+    pub base_volume: Option<f64>,
+    pub quote_volume: Option<f64>,
+    pub mark_price: Option<f64>,
+    pub index_price: Option<f64>,
+    pub info: HashMap<String, serde_json::Value>,
+}
+
+impl Ticker {
+    // fill the fields which can be computed from the others, the same as safeTicker of ccxt
+    pub fn derive(mut self) -> Self {
+        if self.close.is_none() {
+            self.close = self.last;
+        }
+        if let (None, Some(last)) = (self.open, self.last) {
+            if let Some(change) = self.change {
+                self.open = Some(last - change);
+            } else if let Some(percentage) = self.percentage.filter(|v| *v != -100.0) {
+                self.open = Some(last / (1.0 + percentage / 100.0));
+            }
+        }
+        if let (Some(last), Some(open)) = (self.last, self.open) {
+            self.change.get_or_insert(last - open);
+            self.average.get_or_insert((last + open) / 2.0);
+            if open != 0.0 {
+                self.percentage.get_or_insert((last - open) / open * 100.0);
+            }
+        }
+        if let (None, Some(quote_volume), Some(base_volume)) = (self.vwap, self.quote_volume, self.base_volume)
+            && base_volume > 0.0
+        {
+            self.vwap = Some(quote_volume / base_volume);
+        }
+        self
+    }
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct OrderBook {
+    pub symbol: String,
+    // [price, amount], bids sorted by price descending, asks ascending
+    pub bids: Vec<(f64, f64)>,
+    pub asks: Vec<(f64, f64)>,
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    pub nonce: Option<i64>,
+    pub info: Value,
+}
+
+impl OrderBook {
+    pub fn sort(&mut self) {
+        self.bids.sort_by(|a, b| b.0.total_cmp(&a.0));
+        self.asks.sort_by(|a, b| a.0.total_cmp(&b.0));
+    }
+}
+
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct Fee {
+    pub cost: Option<f64>,
+    pub currency: Option<String>,
+    pub rate: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Trade {
+    pub id: String,
+    pub order: Option<String>,
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    pub symbol: String,
+    #[serde(rename = "type")]
+    pub r#type: Option<String>,
+    pub side: Option<String>,
+    pub taker_or_maker: Option<String>,
+    pub price: Option<f64>,
+    pub amount: Option<f64>,
+    pub cost: Option<f64>,
+    pub fee: Option<Fee>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default, Clone, Copy, PartialEq)]
+pub struct OHLCV {
     pub timestamp: i64,
-    pub datetime: String,
+    pub open: f64,
     pub high: f64,
     pub low: f64,
-    pub bid: f64,
-    pub bid_volume: f64,
-    pub ask: f64,
-    pub ask_volume: f64,
-    pub vwap: f64,
-    pub open: f64,
     pub close: f64,
-    pub last: f64,
-    pub previous_close: f64,
-    pub change: f64,
-    pub percentage: f64,
-    pub average: f64,
-    pub base_volume: f64,
-    pub quote_volume: f64,
-    pub info: HashMap<String, serde_json::Value>,
+    pub volume: f64,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingRate {
+    pub symbol: String,
+    pub mark_price: Option<f64>,
+    pub index_price: Option<f64>,
+    // rate of the next settlement
+    pub funding_rate: Option<f64>,
+    pub funding_timestamp: Option<i64>,
+    pub funding_datetime: Option<String>,
+    // 8h, 4h, 1h
+    pub interval: Option<String>,
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct FundingRateHistory {
+    pub symbol: String,
+    pub funding_rate: f64,
+    pub timestamp: i64,
+    pub datetime: Option<String>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct OpenInterest {
+    pub symbol: String,
+    // open interest in contracts
+    pub open_interest_amount: Option<f64>,
+    // open interest in quote currency
+    pub open_interest_value: Option<f64>,
+    pub timestamp: i64,
+    pub datetime: Option<String>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LongShortRatio {
+    pub symbol: String,
+    pub timeframe: String,
+    // number of long accounts / number of short accounts
+    pub long_short_ratio: Option<f64>,
+    pub timestamp: i64,
+    pub datetime: Option<String>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct Volatility {
+    pub timestamp: i64,
+    pub datetime: Option<String>,
+    // annualized, 0.5 for 50%
+    pub volatility: f64,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Greeks {
+    pub symbol: String,
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    pub delta: Option<f64>,
+    pub gamma: Option<f64>,
+    pub theta: Option<f64>,
+    pub vega: Option<f64>,
+    pub rho: Option<f64>,
+    pub bid_size: Option<f64>,
+    pub ask_size: Option<f64>,
+    // implied volatilities are annualized, 0.5 for 50%
+    pub bid_implied_volatility: Option<f64>,
+    pub ask_implied_volatility: Option<f64>,
+    pub mark_implied_volatility: Option<f64>,
+    pub bid_price: Option<f64>,
+    pub ask_price: Option<f64>,
+    pub mark_price: Option<f64>,
+    pub last_price: Option<f64>,
+    pub underlying_price: Option<f64>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct OptionContract {
+    pub symbol: String,
+    // underlying currency
+    pub currency: String,
+    pub strike: Option<f64>,
+    pub expiry: Option<i64>,
+    // call, put
+    pub option_type: Option<String>,
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    pub implied_volatility: Option<f64>,
+    pub open_interest: Option<f64>,
+    pub bid_price: Option<f64>,
+    pub ask_price: Option<f64>,
+    pub mid_price: Option<f64>,
+    pub mark_price: Option<f64>,
+    pub last_price: Option<f64>,
+    pub underlying_price: Option<f64>,
+    pub percentage: Option<f64>,
+    pub base_volume: Option<f64>,
+    pub quote_volume: Option<f64>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Order {
+    pub id: String,
+    pub client_order_id: Option<String>,
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    pub last_trade_timestamp: Option<i64>,
+    pub last_update_timestamp: Option<i64>,
+    // open, closed, canceled, expired, rejected
+    pub status: Option<String>,
+    pub symbol: String,
+    #[serde(rename = "type")]
+    pub r#type: Option<String>,
+    pub time_in_force: Option<String>,
+    pub side: Option<String>,
+    pub price: Option<f64>,
+    pub average: Option<f64>,
+    pub amount: Option<f64>,
+    pub filled: Option<f64>,
+    pub remaining: Option<f64>,
+    pub cost: Option<f64>,
+    pub trigger_price: Option<f64>,
+    pub take_profit_price: Option<f64>,
+    pub stop_loss_price: Option<f64>,
+    pub reduce_only: Option<bool>,
+    pub post_only: Option<bool>,
+    pub trades: Vec<Trade>,
+    pub fee: Option<Fee>,
+    pub info: Value,
+}
+
+// parameters of a new order, built as
+// OrderRequest::new("BTC/USDT:USDT", "limit", "buy", 0.01).price(90000.0).post_only(true)
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct OrderRequest {
+    pub symbol: String,
+    // limit, market
+    pub r#type: String,
+    // buy, sell
+    pub side: String,
+    pub amount: f64,
+    pub price: Option<f64>,
+    // spot market order by quote currency, amount is ignored when set
+    pub cost: Option<f64>,
+    // GTC, IOC, FOK, PO
+    pub time_in_force: Option<String>,
+    pub post_only: bool,
+    pub reduce_only: bool,
+    pub client_order_id: Option<String>,
+    pub trigger_price: Option<f64>,
+    // above, below
+    pub trigger_direction: Option<String>,
+    pub take_profit_price: Option<f64>,
+    pub stop_loss_price: Option<f64>,
+    // 0 one-way mode, 1 buy side of hedge mode, 2 sell side of hedge mode
+    pub position_idx: Option<i64>,
+}
+
+impl OrderRequest {
+    pub fn new(symbol: &str, r#type: &str, side: &str, amount: f64) -> Self {
+        Self {
+            symbol: symbol.to_string(),
+            r#type: r#type.to_string(),
+            side: side.to_string(),
+            amount,
+            ..Default::default()
+        }
+    }
+
+    pub fn price(mut self, price: f64) -> Self {
+        self.price = Some(price);
+        self
+    }
+
+    pub fn cost(mut self, cost: f64) -> Self {
+        self.cost = Some(cost);
+        self
+    }
+
+    pub fn time_in_force(mut self, time_in_force: &str) -> Self {
+        self.time_in_force = Some(time_in_force.to_string());
+        self
+    }
+
+    pub fn post_only(mut self, post_only: bool) -> Self {
+        self.post_only = post_only;
+        self
+    }
+
+    pub fn reduce_only(mut self, reduce_only: bool) -> Self {
+        self.reduce_only = reduce_only;
+        self
+    }
+
+    pub fn client_order_id(mut self, client_order_id: &str) -> Self {
+        self.client_order_id = Some(client_order_id.to_string());
+        self
+    }
+
+    pub fn trigger_price(mut self, trigger_price: f64, direction: &str) -> Self {
+        self.trigger_price = Some(trigger_price);
+        self.trigger_direction = Some(direction.to_string());
+        self
+    }
+
+    pub fn take_profit_price(mut self, take_profit_price: f64) -> Self {
+        self.take_profit_price = Some(take_profit_price);
+        self
+    }
+
+    pub fn stop_loss_price(mut self, stop_loss_price: f64) -> Self {
+        self.stop_loss_price = Some(stop_loss_price);
+        self
+    }
+
+    pub fn position_idx(mut self, position_idx: i64) -> Self {
+        self.position_idx = Some(position_idx);
+        self
+    }
+}
+
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct Balance {
+    pub free: Option<f64>,
+    pub used: Option<f64>,
+    pub total: Option<f64>,
+    pub debt: Option<f64>,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct Balances {
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    // currency code -> balance
+    pub balances: HashMap<String, Balance>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Position {
+    pub id: Option<String>,
+    pub symbol: String,
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    pub last_update_timestamp: Option<i64>,
+    // long, short
+    pub side: Option<String>,
+    pub contracts: Option<f64>,
+    pub contract_size: Option<f64>,
+    pub notional: Option<f64>,
+    pub leverage: Option<f64>,
+    pub entry_price: Option<f64>,
+    pub mark_price: Option<f64>,
+    pub last_price: Option<f64>,
+    pub liquidation_price: Option<f64>,
+    pub unrealized_pnl: Option<f64>,
+    pub realized_pnl: Option<f64>,
+    pub collateral: Option<f64>,
+    pub initial_margin: Option<f64>,
+    pub maintenance_margin: Option<f64>,
+    // cross, isolated
+    pub margin_mode: Option<String>,
+    pub hedged: Option<bool>,
+    // 0 one-way mode, 1 buy side of hedge mode, 2 sell side of hedge mode
+    pub position_idx: Option<i64>,
+    pub stop_loss_price: Option<f64>,
+    pub take_profit_price: Option<f64>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Leverage {
+    pub symbol: String,
+    pub margin_mode: Option<String>,
+    pub long_leverage: Option<f64>,
+    pub short_leverage: Option<f64>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LeverageTier {
+    pub tier: i64,
+    pub symbol: String,
+    pub currency: Option<String>,
+    // notional in (min_notional, max_notional] falls into the tier
+    pub min_notional: f64,
+    pub max_notional: f64,
+    pub maintenance_margin_rate: Option<f64>,
+    pub initial_margin_rate: Option<f64>,
+    // maintenance margin = notional * maintenance_margin_rate - maintenance_margin_deduction
+    pub maintenance_margin_deduction: Option<f64>,
+    pub max_leverage: Option<f64>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default, Clone, Copy, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct MarginRequirement {
+    pub tier: i64,
+    pub leverage: f64,
+    pub initial_margin: f64,
+    pub maintenance_margin: f64,
+}
+
+impl LeverageTier {
+    // margin of a position of notional, tiers are sorted by notional
+    // leverage defaults to and is capped by the max leverage of the tier
+    // None when notional exceeds the last tier or leverage is not positive
+    pub fn margin_requirement(
+        tiers: &[LeverageTier],
+        notional: f64,
+        leverage: Option<f64>,
+    ) -> Option<MarginRequirement> {
+        let notional = notional.abs();
+        let tier = tiers.iter().find(|tier| notional <= tier.max_notional)?;
+        let max_leverage = tier.max_leverage.or(tier.initial_margin_rate.filter(|v| *v > 0.0).map(|v| 1.0 / v))?;
+        // NaN is rejected as well since comparisons with it are false
+        let leverage = match leverage {
+            Some(leverage) if leverage > 0.0 => leverage.min(max_leverage),
+            Some(_) => return None,
+            None => max_leverage,
+        };
+        if leverage <= 0.0 || leverage.is_nan() {
+            return None;
+        }
+        let maintenance_margin_rate = tier.maintenance_margin_rate?;
+        let maintenance_margin =
+            notional * maintenance_margin_rate - tier.maintenance_margin_deduction.unwrap_or_default();
+        Some(MarginRequirement {
+            tier: tier.tier,
+            leverage,
+            initial_margin: notional / leverage,
+            maintenance_margin: maintenance_margin.max(0.0),
+        })
+    }
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TradingFee {
+    pub symbol: String,
+    pub maker: Option<f64>,
+    pub taker: Option<f64>,
+    pub percentage: Option<bool>,
+    pub tier_based: Option<bool>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default, Clone)]
+pub struct TransactionFee {
+    pub fee: Option<f64>,
+    // fee is a rate of the amount when true
+    pub percentage: Option<bool>,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct DepositWithdrawFeeNetwork {
+    pub withdraw: TransactionFee,
+    pub deposit: TransactionFee,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct DepositWithdrawFee {
+    pub withdraw: TransactionFee,
+    pub deposit: TransactionFee,
+    // network code -> fee
+    pub networks: HashMap<String, DepositWithdrawFeeNetwork>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Conversion {
+    pub id: String,
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    pub from_currency: String,
+    pub from_amount: Option<f64>,
+    pub to_currency: String,
+    pub to_amount: Option<f64>,
+    pub price: Option<f64>,
+    pub fee: Option<Fee>,
+    // ms after which a quote can't be executed
+    pub expiry: Option<i64>,
+    // pending, ok, failed
+    pub status: Option<String>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct DepositAddress {
+    pub currency: String,
+    pub network: String,
+    pub address: String,
+    pub tag: Option<String>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct Transaction {
+    pub id: String,
+    pub txid: Option<String>,
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    pub address: Option<String>,
+    pub tag: Option<String>,
+    // deposit, withdrawal
+    #[serde(rename = "type")]
+    pub r#type: String,
+    pub amount: Option<f64>,
+    pub currency: String,
+    // pending, ok, failed, canceled
+    pub status: Option<String>,
+    pub updated: Option<i64>,
+    pub network: Option<String>,
+    pub fee: Option<Fee>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TransferEntry {
+    pub id: String,
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    pub currency: String,
+    pub amount: Option<f64>,
+    pub from_account: Option<String>,
+    pub to_account: Option<String>,
+    // pending, ok, failed
+    pub status: Option<String>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct LedgerEntry {
+    pub id: String,
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    // in, out
+    pub direction: Option<String>,
+    pub account: Option<String>,
+    // id of the trade or order of the entry
+    pub reference_id: Option<String>,
+    // trade, transfer, fee, rebate, cashback, interest, ...
+    #[serde(rename = "type")]
+    pub r#type: Option<String>,
+    pub currency: String,
+    pub symbol: Option<String>,
+    pub amount: Option<f64>,
+    pub before: Option<f64>,
+    pub after: Option<f64>,
+    pub status: Option<String>,
+    pub fee: Option<Fee>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct BorrowRate {
+    pub currency: String,
+    pub rate: Option<f64>,
+    // ms the rate applies to
+    pub period: i64,
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct BorrowInterest {
+    pub currency: String,
+    pub interest: Option<f64>,
+    pub interest_rate: Option<f64>,
+    pub amount_borrowed: Option<f64>,
+    pub margin_mode: String,
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    pub info: Value,
+}
+
+#[derive(Debug, Serialize, Default)]
+pub struct MarginLoan {
+    pub id: Option<String>,
+    pub currency: String,
+    pub amount: Option<f64>,
+    pub symbol: Option<String>,
+    pub timestamp: Option<i64>,
+    pub datetime: Option<String>,
+    pub info: Value,
 }
 
 #[derive(Debug, Serialize, Default)]